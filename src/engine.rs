@@ -1,3 +1,6 @@
+pub mod animation;
 pub mod define;
+pub mod geometry;
 pub mod load;
+pub mod meshopt;
 pub mod scene;
@@ -0,0 +1,229 @@
+use crate::engine;
+
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum Interpolation {
+    Step,
+    #[default]
+    Linear,
+    CubicSpline,
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum AnimationProperty {
+    #[default]
+    Translation,
+    Rotation,
+    Scale,
+    MorphWeights,
+}
+
+#[derive(Clone, Default)]
+pub struct AnimationChannel {
+    // target node index, same as SceneObject.index
+    pub target: u32,
+    pub property: AnimationProperty,
+    pub interpolation: Interpolation,
+    pub times: Vec<f32>,
+    // flattened keyframe values, cubic spline stores (in tangent, value, out tangent) per key
+    pub values: Vec<f32>,
+}
+impl AnimationChannel {
+    fn component_count(&self) -> usize {
+        match self.property {
+            AnimationProperty::Translation => 3,
+            AnimationProperty::Rotation => 4,
+            AnimationProperty::Scale => 3,
+            // one weight per morph target
+            AnimationProperty::MorphWeights => {
+                let values_per_key: usize = self.values.len() / self.times.len();
+                if self.interpolation == Interpolation::CubicSpline {
+                    values_per_key / 3
+                } else {
+                    values_per_key
+                }
+            }
+        }
+    }
+
+    // Value at the given time, clamped to the first and last keyframe
+    pub fn sample(&self, time: f32) -> Vec<f32> {
+        let count: usize = self.component_count();
+        let stride: usize = if self.interpolation == Interpolation::CubicSpline {
+            3 * count
+        } else {
+            count
+        };
+        let value_offset: usize = if self.interpolation == Interpolation::CubicSpline {
+            count
+        } else {
+            0
+        };
+        let key_value = |key: usize| -> &[f32] {
+            let begin: usize = key * stride + value_offset;
+            &self.values[begin..begin + count]
+        };
+
+        let last_key: usize = self.times.len() - 1;
+        if self.times.len() == 1 || time <= self.times[0] {
+            return key_value(0).to_vec();
+        }
+        if time >= self.times[last_key] {
+            return key_value(last_key).to_vec();
+        }
+
+        // keyframe interval containing time
+        let next_key: usize = self.times.partition_point(|key_time| *key_time <= time);
+        let key: usize = next_key - 1;
+        let delta: f32 = self.times[next_key] - self.times[key];
+        let t: f32 = (time - self.times[key]) / delta;
+
+        match self.interpolation {
+            Interpolation::Step => {
+                return key_value(key).to_vec();
+            }
+            Interpolation::Linear => {
+                if self.property == AnimationProperty::Rotation {
+                    let from: glam::Quat = glam::Quat::from_slice(key_value(key));
+                    let to: glam::Quat = glam::Quat::from_slice(key_value(next_key));
+                    return from.slerp(to, t).normalize().to_array().to_vec();
+                }
+                return key_value(key)
+                    .iter()
+                    .zip(key_value(next_key).iter())
+                    .map(|(from, to)| from + (to - from) * t)
+                    .collect::<Vec<_>>();
+            }
+            Interpolation::CubicSpline => {
+                // hermite spline, tangents are scaled by the keyframe delta
+                let t2: f32 = t * t;
+                let t3: f32 = t2 * t;
+                let from_value: &[f32] = key_value(key);
+                let from_out_tangent: &[f32] =
+                    &self.values[key * stride + 2 * count..key * stride + 3 * count];
+                let to_in_tangent: &[f32] =
+                    &self.values[next_key * stride..next_key * stride + count];
+                let to_value: &[f32] = key_value(next_key);
+
+                let mut out_value: Vec<f32> = Vec::with_capacity(count);
+                for i in 0..count {
+                    out_value.push(
+                        (2.0 * t3 - 3.0 * t2 + 1.0) * from_value[i]
+                            + (t3 - 2.0 * t2 + t) * delta * from_out_tangent[i]
+                            + (-2.0 * t3 + 3.0 * t2) * to_value[i]
+                            + (t3 - t2) * delta * to_in_tangent[i],
+                    );
+                }
+                if self.property == AnimationProperty::Rotation {
+                    let rotation: glam::Quat = glam::Quat::from_slice(&out_value).normalize();
+                    return rotation.to_array().to_vec();
+                }
+                return out_value;
+            }
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct AnimationClip {
+    pub name: Option<std::string::String>,
+    pub channels: Vec<AnimationChannel>,
+    // last keyframe time of all channels (second)
+    pub duration: f32,
+}
+
+#[derive(Clone, Default)]
+pub struct AnimationPlayer {
+    pub clip_index: Option<usize>,
+    pub time: f32,
+    pub is_playing: bool,
+    // requestAnimationFrame timestamp of the previous update (millisecond)
+    pub last_timestamp: Option<f64>,
+}
+
+// Update
+
+pub fn update_animation(
+    scene: &std::rc::Rc<std::cell::RefCell<engine::scene::Scene>>,
+    timestamp: f64,
+) {
+    let mut scene_value = scene.borrow_mut();
+
+    let clip_index: usize = match scene_value.animation_player.clip_index {
+        Some(index) if index < scene_value.animations.len() => index,
+        _ => return,
+    };
+
+    // Advance time
+    let duration: f32 = scene_value.animations[clip_index].duration;
+    let last_timestamp: Option<f64> = scene_value.animation_player.last_timestamp;
+    if scene_value.animation_player.is_playing && last_timestamp.is_some() {
+        let delta: f32 = ((timestamp - last_timestamp.unwrap()) / 1000.0) as f32;
+        let mut time: f32 = scene_value.animation_player.time + delta;
+        if duration > 0.0 {
+            time = time % duration;
+        }
+        scene_value.animation_player.time = time;
+    }
+    scene_value.animation_player.last_timestamp = Some(timestamp);
+
+    // Evaluate local transforms from rest pose
+    let time: f32 = scene_value.animation_player.time;
+    let mut translations: Vec<[f32; 3]> = scene_value
+        .objects
+        .iter()
+        .map(|object| object.translation)
+        .collect::<Vec<_>>();
+    let mut rotations: Vec<[f32; 4]> = scene_value
+        .objects
+        .iter()
+        .map(|object| object.rotation)
+        .collect::<Vec<_>>();
+    let mut scales: Vec<[f32; 3]> = scene_value
+        .objects
+        .iter()
+        .map(|object| object.scale)
+        .collect::<Vec<_>>();
+    // weights are written over the current ones, GUI edits stay on unanimated objects
+    let mut morph_weights: Vec<(usize, Vec<f32>)> = Vec::new();
+
+    for channel in scene_value.animations[clip_index].channels.iter() {
+        let target: usize = channel.target as usize;
+        if target >= translations.len() || channel.times.is_empty() {
+            continue;
+        }
+        let value: Vec<f32> = channel.sample(time);
+        match channel.property {
+            AnimationProperty::Translation => {
+                translations[target] = [value[0], value[1], value[2]];
+            }
+            AnimationProperty::Rotation => {
+                rotations[target] = [value[0], value[1], value[2], value[3]];
+            }
+            AnimationProperty::Scale => {
+                scales[target] = [value[0], value[1], value[2]];
+            }
+            AnimationProperty::MorphWeights => {
+                morph_weights.push((target, value));
+            }
+        }
+    }
+
+    let local_matrices: Vec<glam::Mat4> = (0..translations.len())
+        .map(|i| {
+            glam::Mat4::from_scale_rotation_translation(
+                glam::Vec3::from_array(scales[i]),
+                glam::Quat::from_array(rotations[i]),
+                glam::Vec3::from_array(translations[i]),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    scene_value.update_world_transforms(&local_matrices);
+
+    for (target, weights) in morph_weights {
+        let object_weights: &mut Vec<f32> = &mut scene_value.objects[target].morph_weights;
+        for (i, weight) in weights.iter().enumerate().take(object_weights.len()) {
+            object_weights[i] = *weight;
+        }
+    }
+}
@@ -4,6 +4,10 @@ pub const CANVAS_ELEMENT_ID: &str = "canvas";
 // Rendering constants
 pub const VS_ENTRY_POINT: &str = "vs_main";
 pub const FS_ENTRY_POINT: &str = "fs_main";
+// material index is written to a 16 bit float gbuffer channel, exact up to 2048
+pub const MAX_MATERIAL_COUNT: usize = 2048;
+// punctual lights of a scene, the light buffer is allocated once for any scene loaded later
+pub const MAX_LIGHT_COUNT: usize = 256;
 
 // .gltf from Sketchfab.com
 #[allow(dead_code)]
@@ -0,0 +1,258 @@
+use crate::rendering;
+
+// Tangents
+
+struct TangentGeometry<'a> {
+    vertices: &'a mut Vec<rendering::common::Vertex>,
+    indices: &'a [u32],
+}
+
+impl bevy_mikktspace::Geometry for TangentGeometry<'_> {
+    fn num_faces(&self) -> usize {
+        return self.indices.len() / 3;
+    }
+
+    fn num_vertices_of_face(&self, _face: usize) -> usize {
+        return 3;
+    }
+
+    fn position(&self, face: usize, vert: usize) -> [f32; 3] {
+        let pos: [f32; 4] = self.vertices[self.indices[face * 3 + vert] as usize].pos;
+        return [pos[0], pos[1], pos[2]];
+    }
+
+    fn normal(&self, face: usize, vert: usize) -> [f32; 3] {
+        return self.vertices[self.indices[face * 3 + vert] as usize].normal;
+    }
+
+    fn tex_coord(&self, face: usize, vert: usize) -> [f32; 2] {
+        return self.vertices[self.indices[face * 3 + vert] as usize].uv;
+    }
+
+    fn set_tangent_encoded(&mut self, tangent: [f32; 4], face: usize, vert: usize) {
+        self.vertices[self.indices[face * 3 + vert] as usize].tangent = tangent;
+    }
+}
+
+// MikkTSpace tangents as required by the glTF spec, w keeps the bitangent sign
+pub fn generate_tangents(vertices: &mut Vec<rendering::common::Vertex>, indices: &[u32]) -> bool {
+    let mut geometry: TangentGeometry<'_> = TangentGeometry {
+        vertices: vertices,
+        indices: indices,
+    };
+
+    return bevy_mikktspace::generate_tangents(&mut geometry);
+}
+
+// Normals
+
+// Every triangle gets its own vertices so each corner can carry the face normal
+fn unweld_vertices(vertices: &mut Vec<rendering::common::Vertex>, indices: &mut Vec<u32>) {
+    let unwelded: Vec<rendering::common::Vertex> = indices
+        .iter()
+        .map(|index| vertices[*index as usize])
+        .collect::<Vec<_>>();
+
+    *vertices = unwelded;
+    *indices = (0..vertices.len() as u32).collect::<Vec<_>>();
+}
+
+fn get_face_normal(vertices: &[rendering::common::Vertex], face: usize) -> glam::Vec3 {
+    let p0: glam::Vec3 = glam::Vec4::from_array(vertices[face * 3].pos).truncate();
+    let p1: glam::Vec3 = glam::Vec4::from_array(vertices[face * 3 + 1].pos).truncate();
+    let p2: glam::Vec3 = glam::Vec4::from_array(vertices[face * 3 + 2].pos).truncate();
+
+    // not normalized, the length weights smoothing by triangle area
+    return (p1 - p0).cross(p2 - p0);
+}
+
+// Flat normals as required by the glTF spec when NORMAL is missing
+pub fn generate_flat_normals(
+    vertices: &mut Vec<rendering::common::Vertex>,
+    indices: &mut Vec<u32>,
+) {
+    unweld_vertices(vertices, indices);
+
+    for face in 0..vertices.len() / 3 {
+        let normal: [f32; 3] = get_face_normal(vertices, face)
+            .normalize_or_zero()
+            .to_array();
+        for corner in 0..3 {
+            vertices[face * 3 + corner].normal = normal;
+        }
+    }
+}
+
+// Smooth normals across faces meeting at a position with less than smoothing_angle (degree) between them
+pub fn generate_smooth_normals(
+    vertices: &mut Vec<rendering::common::Vertex>,
+    indices: &mut Vec<u32>,
+    smoothing_angle: f32,
+) {
+    unweld_vertices(vertices, indices);
+
+    let face_count: usize = vertices.len() / 3;
+    let face_normals: Vec<glam::Vec3> = (0..face_count)
+        .map(|face| get_face_normal(vertices, face))
+        .collect::<Vec<_>>();
+
+    // faces touching each position
+    let mut position_faces: std::collections::HashMap<[u32; 3], Vec<usize>> =
+        std::collections::HashMap::new();
+    for (i, vertex) in vertices.iter().enumerate() {
+        let key: [u32; 3] = [
+            vertex.pos[0].to_bits(),
+            vertex.pos[1].to_bits(),
+            vertex.pos[2].to_bits(),
+        ];
+        position_faces.entry(key).or_default().push(i / 3);
+    }
+
+    let cos_threshold: f32 = smoothing_angle.to_radians().cos();
+    for i in 0..vertices.len() {
+        let face: usize = i / 3;
+        let face_normal: glam::Vec3 = face_normals[face].normalize_or_zero();
+        let key: [u32; 3] = [
+            vertices[i].pos[0].to_bits(),
+            vertices[i].pos[1].to_bits(),
+            vertices[i].pos[2].to_bits(),
+        ];
+
+        let mut normal: glam::Vec3 = glam::Vec3::ZERO;
+        for other in position_faces[&key].iter() {
+            if face_normals[*other].normalize_or_zero().dot(face_normal) >= cos_threshold {
+                normal += face_normals[*other];
+            }
+        }
+        if normal == glam::Vec3::ZERO {
+            normal = face_normal;
+        }
+        vertices[i].normal = normal.normalize_or_zero().to_array();
+    }
+}
+
+// Topology
+
+// Strip triangles alternate winding, odd ones swap their last two corners
+pub fn triangle_strip_to_list(indices: &[u32]) -> Vec<u32> {
+    let mut list: Vec<u32> = Vec::new();
+    for i in 0..indices.len().saturating_sub(2) {
+        list.extend_from_slice(&[indices[i], indices[i + 1 + i % 2], indices[i + 2 - i % 2]]);
+    }
+    return list;
+}
+
+pub fn triangle_fan_to_list(indices: &[u32]) -> Vec<u32> {
+    let mut list: Vec<u32> = Vec::new();
+    for i in 1..indices.len().saturating_sub(1) {
+        list.extend_from_slice(&[indices[i], indices[i + 1], indices[0]]);
+    }
+    return list;
+}
+
+// A loop also closes the last vertex back to the first
+pub fn line_strip_to_list(indices: &[u32], is_loop: bool) -> Vec<u32> {
+    let mut list: Vec<u32> = Vec::new();
+    for i in 0..indices.len().saturating_sub(1) {
+        list.extend_from_slice(&[indices[i], indices[i + 1]]);
+    }
+    if is_loop && indices.len() > 2 {
+        list.extend_from_slice(&[indices[indices.len() - 1], indices[0]]);
+    }
+    return list;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_vertices(positions: &[[f32; 3]]) -> Vec<rendering::common::Vertex> {
+        return positions
+            .iter()
+            .map(|position| rendering::common::Vertex {
+                pos: [position[0], position[1], position[2], 1.0],
+                ..bytemuck::Zeroable::zeroed()
+            })
+            .collect::<Vec<_>>();
+    }
+
+    fn assert_normal(vertex: &rendering::common::Vertex, expected: [f32; 3]) {
+        let normal: glam::Vec3 = glam::Vec3::from_array(vertex.normal);
+        assert!(
+            normal.abs_diff_eq(glam::Vec3::from_array(expected), 1e-5),
+            "{:?} != {:?}",
+            vertex.normal,
+            expected
+        );
+    }
+
+    // two right triangles meeting at 90 degrees along the y axis, +z and +x facing
+    fn get_corner() -> (Vec<rendering::common::Vertex>, Vec<u32>) {
+        let vertices: Vec<rendering::common::Vertex> = get_vertices(&[
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [0.0, 0.0, 1.0],
+        ]);
+        return (vertices, vec![0, 1, 2, 0, 2, 3]);
+    }
+
+    #[test]
+    fn strip_alternates_winding() {
+        assert_eq!(
+            triangle_strip_to_list(&[0, 1, 2, 3, 4]),
+            vec![0, 1, 2, 1, 3, 2, 2, 3, 4]
+        );
+        assert!(triangle_strip_to_list(&[0, 1]).is_empty());
+    }
+
+    #[test]
+    fn fan_keeps_first_vertex() {
+        assert_eq!(triangle_fan_to_list(&[0, 1, 2, 3]), vec![1, 2, 0, 2, 3, 0]);
+    }
+
+    #[test]
+    fn loop_closes_to_first_vertex() {
+        assert_eq!(line_strip_to_list(&[0, 1, 2], false), vec![0, 1, 1, 2]);
+        assert_eq!(line_strip_to_list(&[0, 1, 2], true), vec![0, 1, 1, 2, 2, 0]);
+    }
+
+    #[test]
+    fn flat_normals_unweld() {
+        let (mut vertices, mut indices) = get_corner();
+        generate_flat_normals(&mut vertices, &mut indices);
+
+        assert_eq!(vertices.len(), 6);
+        assert_eq!(indices, vec![0, 1, 2, 3, 4, 5]);
+        for i in 0..3 {
+            assert_normal(&vertices[i], [0.0, 0.0, 1.0]);
+            assert_normal(&vertices[i + 3], [1.0, 0.0, 0.0]);
+        }
+    }
+
+    #[test]
+    fn smooth_normals_threshold() {
+        // below the threshold the 90 degree edge stays hard
+        let (mut vertices, mut indices) = get_corner();
+        generate_smooth_normals(&mut vertices, &mut indices, 45.0);
+        for i in 0..3 {
+            assert_normal(&vertices[i], [0.0, 0.0, 1.0]);
+            assert_normal(&vertices[i + 3], [1.0, 0.0, 0.0]);
+        }
+
+        // above it the shared edge is averaged, other corners keep the face normal
+        let (mut vertices, mut indices) = get_corner();
+        generate_smooth_normals(&mut vertices, &mut indices, 120.0);
+        let shared: [f32; 3] = [
+            std::f32::consts::FRAC_1_SQRT_2,
+            0.0,
+            std::f32::consts::FRAC_1_SQRT_2,
+        ];
+        assert_normal(&vertices[0], shared);
+        assert_normal(&vertices[1], [0.0, 0.0, 1.0]);
+        assert_normal(&vertices[2], shared);
+        assert_normal(&vertices[3], shared);
+        assert_normal(&vertices[4], shared);
+        assert_normal(&vertices[5], [1.0, 0.0, 0.0]);
+    }
+}
@@ -1,6 +1,154 @@
 use crate::engine;
 use crate::rendering;
 
+// Error
+
+#[derive(Debug)]
+pub enum LoadError {
+    // fatal
+    Fetch {
+        path: String,
+        message: String,
+    },
+    Parse {
+        path: String,
+        message: String,
+    },
+    MissingBinChunk,
+    InvalidDataUri,
+    Meshopt {
+        view: usize,
+        message: String,
+    },
+    // recoverable
+    Texture {
+        path: String,
+        message: String,
+    },
+    UnnamedNode(usize),
+    UnnamedMesh(usize),
+    UnnamedMaterial(usize),
+    TangentGeneration {
+        mesh: usize,
+        primitive: usize,
+    },
+    VertexAttribute {
+        mesh: usize,
+        primitive: usize,
+        attribute: &'static str,
+    },
+    IndexRange {
+        mesh: usize,
+        primitive: usize,
+    },
+    MorphTarget {
+        mesh: usize,
+        primitive: usize,
+        target: usize,
+    },
+    AnimationChannel {
+        animation: usize,
+        channel: usize,
+    },
+    SpecularGlossinessLayout(usize),
+    ExtensionTexture {
+        material: usize,
+        extension: &'static str,
+    },
+}
+impl std::fmt::Display for LoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoadError::Fetch { path, message } => {
+                write!(f, "Failed to fetch {} : {}", path, message)
+            }
+            LoadError::Parse { path, message } => {
+                write!(f, "Failed to parse {} : {}", path, message)
+            }
+            LoadError::MissingBinChunk => write!(f, ".glb has no BIN chunk"),
+            LoadError::InvalidDataUri => write!(f, "Invalid base64 data uri"),
+            LoadError::Meshopt { view, message } => {
+                write!(f, "Failed to decode buffer view {} : {}", view, message)
+            }
+            LoadError::Texture { path, message } => {
+                write!(f, "Failed to load texture {} : {}", path, message)
+            }
+            LoadError::UnnamedNode(index) => write!(f, "Node {} has no name", index),
+            LoadError::UnnamedMesh(index) => write!(f, "Mesh {} has no name", index),
+            LoadError::UnnamedMaterial(index) => write!(f, "Material {} has no name", index),
+            LoadError::TangentGeneration { mesh, primitive } => write!(
+                f,
+                "Mesh {} primitive {} failed to generate tangents",
+                mesh, primitive
+            ),
+            LoadError::VertexAttribute {
+                mesh,
+                primitive,
+                attribute,
+            } => write!(
+                f,
+                "Mesh {} primitive {} has an unreadable {}, using the default",
+                mesh, primitive, attribute
+            ),
+            LoadError::IndexRange { mesh, primitive } => write!(
+                f,
+                "Mesh {} primitive {} has indices out of range, skipped",
+                mesh, primitive
+            ),
+            LoadError::MorphTarget {
+                mesh,
+                primitive,
+                target,
+            } => write!(
+                f,
+                "Mesh {} primitive {} morph target {} does not match the vertex count, resized",
+                mesh, primitive, target
+            ),
+            LoadError::AnimationChannel { animation, channel } => write!(
+                f,
+                "Animation {} channel {} has mismatched or unordered keyframes, skipped",
+                animation, channel
+            ),
+            LoadError::SpecularGlossinessLayout(index) => write!(
+                f,
+                "Material {} specular glossiness and diffuse uv layouts differ, using the diffuse one",
+                index
+            ),
+            LoadError::ExtensionTexture {
+                material,
+                extension,
+            } => write!(
+                f,
+                "Material {} has {} textures, only the factors are used",
+                material, extension
+            ),
+        }
+    }
+}
+impl std::error::Error for LoadError {}
+
+#[derive(Clone, Copy, Default)]
+pub struct LoadOptions {
+    // None computes flat normals for primitives without NORMAL, Some smooths below this angle (degree)
+    pub normal_smoothing_angle: Option<f32>,
+    // None loads the default scene
+    pub scene_index: Option<usize>,
+}
+
+#[derive(Default)]
+pub struct GltfScene {
+    pub objects: Vec<engine::scene::SceneObject>,
+    pub materials: Vec<engine::scene::SceneMaterial>,
+    pub skins: Vec<engine::scene::SceneSkin>,
+    pub animations: Vec<engine::animation::AnimationClip>,
+    pub cameras: Vec<engine::scene::SceneCamera>,
+    pub lights: Vec<engine::scene::SceneLight>,
+    pub scenes: Vec<std::string::String>,
+    pub active_scene: Option<usize>,
+    // recoverable problems, loaded with fallback
+    pub warnings: Vec<LoadError>,
+}
+
 // Utility
 
 #[allow(dead_code)]
@@ -48,6 +196,7 @@ pub async fn load_binary(file_name: &str) -> anyhow::Result<Vec<u8>> {
             let url = format_url(file_name);
             let data = reqwest::get(url)
                 .await?
+                .error_for_status()?
                 .bytes()
                 .await?
                 .to_vec();
@@ -63,7 +212,7 @@ pub async fn load_binary(file_name: &str) -> anyhow::Result<Vec<u8>> {
     Ok(data)
 }
 #[allow(dead_code)]
-async fn extract_texture_data(file_name: &String) -> (Vec<u8>, [u32; 2]) {
+async fn extract_texture_data(file_name: &String) -> Result<(Vec<u8>, [u32; 2]), LoadError> {
     let mut out_data: Vec<u8> = Vec::new();
     let mut out_size: [u32; 2] = [1, 1];
 
@@ -88,12 +237,38 @@ async fn extract_texture_data(file_name: &String) -> (Vec<u8>, [u32; 2]) {
 
     // Load .rgba - ref : bin/image_convert.rs
     if is_exist_rgba {
-        let texture_data = load_binary(&rgba_path.as_str())
-            .await
-            .expect("Failed to load texture");
+        let texture_data =
+            load_binary(&rgba_path.as_str())
+                .await
+                .map_err(|error| LoadError::Texture {
+                    path: rgba_path.clone(),
+                    message: error.to_string(),
+                })?;
 
+        if texture_data.len() < 8 {
+            return Err(LoadError::Texture {
+                path: rgba_path,
+                message: "no .rgba header".to_string(),
+            });
+        }
         let data_width: u32 = load_4byte_to_u32(&texture_data[0..4]);
         let data_height: u32 = load_4byte_to_u32(&texture_data[4..8]);
+        // a malformed header must not overflow
+        let data_size: Option<usize> = (data_width as usize)
+            .checked_mul(data_height as usize)
+            .and_then(|size| size.checked_mul(4));
+        if data_size.is_none() {
+            return Err(LoadError::Texture {
+                path: rgba_path,
+                message: "invalid .rgba header size".to_string(),
+            });
+        }
+        if texture_data.len() - 8 != data_size.unwrap() {
+            return Err(LoadError::Texture {
+                path: rgba_path,
+                message: "size mismatch with .rgba header".to_string(),
+            });
+        }
 
         out_data = texture_data[8..texture_data.len()].to_vec();
         out_size = [data_width, data_height];
@@ -117,7 +292,78 @@ async fn extract_texture_data(file_name: &String) -> (Vec<u8>, [u32; 2]) {
         }
     }*/
 
-    return (out_data, out_size);
+    return Ok((out_data, out_size));
+}
+async fn get_gltf_texture_data(
+    image_source: &gltf::image::Source<'_>,
+    buffer_data: &Vec<Vec<u8>>,
+    gltf_folder_path: &str,
+) -> Result<(Vec<u8>, [u32; 2]), LoadError> {
+    match image_source {
+        gltf::image::Source::View { view, mime_type } => {
+            // embedded in buffer view, a truncated buffer is reported instead of indexed
+            let path: String = format!("buffer view {}", view.index());
+            let begin: usize = view.offset();
+            let end: usize = begin + view.length();
+            let data: &[u8] = buffer_data
+                .get(view.buffer().index())
+                .and_then(|buffer| buffer.get(begin..end))
+                .ok_or(LoadError::Texture {
+                    path: path.clone(),
+                    message: "buffer view is out of range".to_string(),
+                })?;
+            decode_texture_data(data, mime_type)
+                .map_err(|message| LoadError::Texture { path, message })
+        }
+        gltf::image::Source::Uri { uri, mime_type } => {
+            if is_data_uri(uri) {
+                // embedded in base64 data uri
+                let (data_mime_type, data) = decode_data_uri(uri)?;
+                decode_texture_data(&data, mime_type.unwrap_or(&data_mime_type)).map_err(
+                    |message| LoadError::Texture {
+                        path: "data uri".to_string(),
+                        message,
+                    },
+                )
+            } else {
+                // from url
+                let texture_path = gltf_folder_path.to_string() + uri;
+                extract_texture_data(&texture_path).await
+            }
+        }
+    }
+}
+fn decode_texture_data(bytes: &[u8], mime_type: &str) -> Result<(Vec<u8>, [u32; 2]), String> {
+    let format: image::ImageFormat = match mime_type {
+        "image/png" => image::ImageFormat::Png,
+        "image/jpeg" => image::ImageFormat::Jpeg,
+        _ => image::guess_format(bytes).map_err(|error| error.to_string())?,
+    };
+    let texture_image: image::DynamicImage =
+        image::load_from_memory_with_format(bytes, format).map_err(|error| error.to_string())?;
+
+    Ok((
+        texture_image.to_rgba8().into_raw(),
+        [texture_image.width(), texture_image.height()],
+    ))
+}
+fn is_data_uri(uri: &str) -> bool {
+    uri.starts_with("data:")
+}
+fn decode_data_uri(uri: &str) -> Result<(String, Vec<u8>), LoadError> {
+    // data:[<mime type>][;base64],<data>
+    let body: &str = uri.strip_prefix("data:").ok_or(LoadError::InvalidDataUri)?;
+    let (header, data) = body.split_once(',').ok_or(LoadError::InvalidDataUri)?;
+    let mime_type: &str = header
+        .strip_suffix(";base64")
+        .ok_or(LoadError::InvalidDataUri)?;
+    let decoded: Vec<u8> = base64::decode(data).map_err(|_| LoadError::InvalidDataUri)?;
+
+    Ok((mime_type.to_string(), decoded))
+}
+fn is_glb(bytes: &[u8]) -> bool {
+    // .glb header : magic "glTF", version, length
+    bytes.len() >= 12 && &bytes[0..4] == b"glTF"
 }
 #[allow(dead_code)]
 fn load_4byte_to_u32(bytes: &[u8]) -> u32 {
@@ -129,57 +375,236 @@ fn load_4byte_to_u32(bytes: &[u8]) -> u32 {
     return out_value;
 }
 
+// Required extensions the gltf crate does not enable but this loader decodes itself
+const DECODED_EXTENSIONS: [&str; 3] = [
+    "KHR_mesh_quantization",
+    "EXT_meshopt_compression",
+    "KHR_texture_transform",
+];
+
+// Same checks as gltf::Gltf::from_slice, except for extensions in DECODED_EXTENSIONS
+fn validate_gltf(gltf: &gltf::Gltf) -> Result<(), String> {
+    let root: &gltf::json::Root = gltf.document.as_json();
+    let mut errors: Vec<String> = Vec::new();
+    gltf::json::validation::Validate::validate(
+        root,
+        root,
+        gltf::json::Path::new,
+        &mut |path, error| {
+            let path: gltf::json::Path = path();
+            if !path.as_str().starts_with("extensionsRequired") {
+                errors.push(format!("{} : {}", path, error));
+            }
+        },
+    );
+    for extension in root.extensions_required.iter() {
+        if !gltf::json::extensions::ENABLED_EXTENSIONS.contains(&extension.as_str())
+            && !DECODED_EXTENSIONS.contains(&extension.as_str())
+        {
+            errors.push(format!("required extension {} is not supported", extension));
+        }
+    }
+
+    if errors.is_empty() {
+        return Ok(());
+    }
+    return Err(errors.join(", "));
+}
+
+// Decoded bytes of an EXT_meshopt_compression buffer view
+fn get_meshopt_view_data(
+    extension: &gltf::json::Value,
+    buffer_data: &Vec<Vec<u8>>,
+) -> Result<Vec<u8>, String> {
+    let get_usize = |key: &str| -> Option<usize> {
+        extension
+            .get(key)
+            .and_then(|value| value.as_u64())
+            .map(|value| value as usize)
+    };
+    let buffer: usize = get_usize("buffer").ok_or("missing buffer".to_string())?;
+    let offset: usize = get_usize("byteOffset").unwrap_or(0);
+    let length: usize = get_usize("byteLength").ok_or("missing byteLength".to_string())?;
+    let stride: usize = get_usize("byteStride").ok_or("missing byteStride".to_string())?;
+    let count: usize = get_usize("count").ok_or("missing count".to_string())?;
+
+    let mode: engine::meshopt::CompressionMode =
+        match extension.get("mode").and_then(|value| value.as_str()) {
+            Some("ATTRIBUTES") => engine::meshopt::CompressionMode::Attributes,
+            Some("TRIANGLES") => engine::meshopt::CompressionMode::Triangles,
+            Some("INDICES") => engine::meshopt::CompressionMode::Indices,
+            mode => return Err(format!("invalid mode {:?}", mode)),
+        };
+    let filter: engine::meshopt::CompressionFilter =
+        match extension.get("filter").and_then(|value| value.as_str()) {
+            None | Some("NONE") => engine::meshopt::CompressionFilter::None,
+            Some("OCTAHEDRAL") => engine::meshopt::CompressionFilter::Octahedral,
+            Some("QUATERNION") => engine::meshopt::CompressionFilter::Quaternion,
+            Some("EXPONENTIAL") => engine::meshopt::CompressionFilter::Exponential,
+            Some(filter) => return Err(format!("invalid filter {}", filter)),
+        };
+
+    let source: &[u8] = buffer_data
+        .get(buffer)
+        .and_then(|data| data.get(offset..offset + length))
+        .ok_or("compressed data is out of buffer range".to_string())?;
+    return engine::meshopt::decode_buffer_view(source, count, stride, mode, filter);
+}
+
 // Load .gltf
 
 pub async fn load_gltf_scene(
     file_name: &str,
-) -> (
-    Vec<engine::scene::SceneObject>,
-    Vec<engine::scene::SceneMaterial>,
-) {
-    let gltf_text = load_string(file_name)
+    options: &LoadOptions,
+) -> Result<GltfScene, LoadError> {
+    // .glb is a binary container, .gltf is a json text
+    let gltf_binary: Vec<u8> = load_binary(file_name)
         .await
-        .expect("Failed to parse .gltf file path string");
-    let gltf_cursor: std::io::Cursor<String> = std::io::Cursor::new(gltf_text);
-    let gltf_reader: std::io::BufReader<_> = std::io::BufReader::new(gltf_cursor);
-
-    let gltf: gltf::Gltf = gltf::Gltf::from_reader(gltf_reader).expect("Failed to read .gltf file");
+        .map_err(|error| LoadError::Fetch {
+            path: file_name.to_string(),
+            message: error.to_string(),
+        })?;
+    // validated below, the gltf crate rejects required extensions decoded here
+    let gltf: gltf::Gltf = if is_glb(&gltf_binary) {
+        gltf::Gltf::from_slice_without_validation(&gltf_binary)
+    } else {
+        let gltf_cursor: std::io::Cursor<Vec<u8>> = std::io::Cursor::new(gltf_binary);
+        let gltf_reader: std::io::BufReader<_> = std::io::BufReader::new(gltf_cursor);
+        gltf::Gltf::from_reader_without_validation(gltf_reader)
+    }
+    .map_err(|error| LoadError::Parse {
+        path: file_name.to_string(),
+        message: error.to_string(),
+    })?;
+    validate_gltf(&gltf).map_err(|message| LoadError::Parse {
+        path: file_name.to_string(),
+        message: message,
+    })?;
     let mut buffer_data: Vec<Vec<u8>> = Vec::new();
 
-    let slash_num: usize = file_name.rfind("/").unwrap() + 1;
+    let slash_num: usize = file_name.rfind("/").map_or(0, |index| index + 1);
     let folder_path = file_name.split_at(slash_num).0;
 
     for buffer in gltf.buffers() {
+        // EXT_meshopt_compression fallback buffers have no data, decoded views are written into them
+        let is_meshopt_fallback: bool = buffer
+            .extension_value("EXT_meshopt_compression")
+            .and_then(|extension| extension.get("fallback"))
+            .and_then(|fallback| fallback.as_bool())
+            .unwrap_or(false);
+        if is_meshopt_fallback {
+            buffer_data.push(vec![0; buffer.length()]);
+            continue;
+        }
+
         match buffer.source() {
             gltf::buffer::Source::Bin => {
-                //if let Some(blob) = gltf.blob.as_deref() {
-                //    buffer_data.push(blob.into());
-                //    log::debug!("Found a bin, saving");
-                //};
+                // BIN chunk of .glb
+                let blob: &[u8] = gltf.blob.as_deref().ok_or(LoadError::MissingBinChunk)?;
+                buffer_data.push(blob.to_vec());
             }
             gltf::buffer::Source::Uri(uri) => {
+                if is_data_uri(uri) {
+                    let (_, bin) = decode_data_uri(uri)?;
+                    buffer_data.push(bin);
+                    continue;
+                }
                 let binary_path = folder_path.to_string() + uri;
-                let bin = load_binary(&binary_path.as_str())
-                    .await
-                    .expect("Failed to load binary");
+                let bin =
+                    load_binary(&binary_path.as_str())
+                        .await
+                        .map_err(|error| LoadError::Fetch {
+                            path: binary_path.clone(),
+                            message: error.to_string(),
+                        })?;
                 buffer_data.push(bin);
             }
         }
     }
 
+    // EXT_meshopt_compression views are decoded in place, accessors read them like plain views
+    for view in gltf.views() {
+        let extension: &gltf::json::Value = match view.extension_value("EXT_meshopt_compression") {
+            Some(extension) => extension,
+            None => continue,
+        };
+        let decoded: Vec<u8> =
+            get_meshopt_view_data(extension, &buffer_data).map_err(|message| {
+                LoadError::Meshopt {
+                    view: view.index(),
+                    message: message,
+                }
+            })?;
+        let target: &mut Vec<u8> = &mut buffer_data[view.buffer().index()];
+        let end: usize = view.offset() + decoded.len();
+        if target.len() < end {
+            target.resize(end, 0);
+        }
+        target[view.offset()..end].copy_from_slice(&decoded);
+    }
+
     let mut out_objects: Vec<engine::scene::SceneObject> = Vec::new();
     let mut out_materials: Vec<engine::scene::SceneMaterial> = Vec::new();
+    let mut out_warnings: Vec<LoadError> = Vec::new();
     let mut num_node: u32 = 0;
     let mut num_verts: u32 = 0;
     let mut num_indices: u32 = 0;
 
+    // Pick the scene, files without scenes show every root node
+    let gltf_scene: Option<gltf::Scene<'_>> = match options.scene_index {
+        Some(index) => gltf.scenes().nth(index),
+        None => gltf.default_scene().or(gltf.scenes().next()),
+    };
+    let root_nodes: Vec<gltf::Node<'_>> = match gltf_scene.as_ref() {
+        Some(gltf_scene) => gltf_scene.nodes().collect::<Vec<_>>(),
+        None => {
+            let mut is_child: Vec<bool> = vec![false; gltf.nodes().len()];
+            for node in gltf.nodes() {
+                for child in node.children() {
+                    is_child[child.index()] = true;
+                }
+            }
+            gltf.nodes()
+                .filter(|node| is_child[node.index()] == false)
+                .collect::<Vec<_>>()
+        }
+    };
+
+    // Traverse from root nodes, parents are placed before their children
+    let mut reachable_nodes: Vec<(gltf::Node<'_>, Option<u32>)> = Vec::new();
+    let mut node_to_object: Vec<Option<u32>> = vec![None; gltf.nodes().len()];
+    {
+        let mut node_stack: Vec<(gltf::Node<'_>, Option<u32>)> = root_nodes
+            .into_iter()
+            .rev()
+            .map(|node| (node, None))
+            .collect::<Vec<_>>();
+        while let Some((node, parent)) = node_stack.pop() {
+            if node_to_object[node.index()].is_some() {
+                continue;
+            }
+            let object_index: u32 = reachable_nodes.len() as u32;
+            node_to_object[node.index()] = Some(object_index);
+            for child in node.children().collect::<Vec<_>>().into_iter().rev() {
+                node_stack.push((child, Some(object_index)));
+            }
+            reachable_nodes.push((node, parent));
+        }
+    }
+
     // Create scene object from meshes
-    for node in gltf.nodes() {
+    for (object_index, (node, parent)) in reachable_nodes.iter().enumerate() {
         //log::debug!("Node : {}", node.name().unwrap());
 
         let mut mesh: Option<rendering::common::Mesh> = None;
         if node.mesh().is_some() {
-            mesh = Some(get_gltf_mesh_from_node(&node, &buffer_data));
+            mesh = Some(get_gltf_mesh_from_node(
+                node,
+                &buffer_data,
+                gltf.materials().len() as u32,
+                options,
+                &mut out_warnings,
+            ));
         }
 
         // Debug only
@@ -189,45 +614,49 @@ pub async fn load_gltf_scene(
             num_indices += mesh.as_ref().unwrap().indices.len() as u32;
         }
 
+        if node.name().is_none() {
+            out_warnings.push(LoadError::UnnamedNode(node.index()));
+        }
+
+        // node weights override the mesh default weights
+        let morph_target_count: usize = mesh.as_ref().map_or(0, |mesh| mesh.morph_targets.len());
+        let mut morph_weights: Vec<f32> = node
+            .weights()
+            .or(node.mesh().and_then(|mesh| mesh.weights()))
+            .map_or(Vec::new(), |weights| weights.to_vec());
+        morph_weights.resize(morph_target_count, 0.0);
+
+        let (translation, rotation, scale) = node.transform().decomposed();
         let mut scene_object = engine::scene::SceneObject {
-            _name: Some(node.name().unwrap().to_string()),
+            _name: node.name().map(|name| name.to_string()),
             shading_type: 44,
             world_transform: node.transform().matrix(),
+            translation: translation,
+            rotation: rotation,
+            scale: scale,
             source_mesh: if mesh.is_some() {
                 Some(std::rc::Rc::new(std::cell::RefCell::new(mesh.unwrap())))
             } else {
                 None
             },
             render_resource: None,
-            index: node.index() as u32,
+            index: object_index as u32,
+            parent_index: *parent,
+            skin: node.skin().map(|skin| skin.index() as u32),
+            is_dynamic: node.skin().is_some() || morph_target_count > 0,
+            morph_weights: morph_weights,
             ..Default::default()
         };
 
         for child in node.children().into_iter() {
-            scene_object.child_index.push(child.index() as u32);
+            if let Some(child_object) = node_to_object[child.index()] {
+                scene_object.child_index.push(child_object);
+            }
         }
 
         out_objects.push(scene_object);
     }
 
-    // Build parent tree
-    let mut parent_vec: Vec<Option<u32>> = vec![None; out_objects.len()];
-    {
-        for object in out_objects.iter() {
-            for child in object.child_index.iter() {
-                let inner = parent_vec.get_mut(*child as usize).unwrap();
-                inner.replace(object.index);
-            }
-        }
-        for i in 0..parent_vec.len() {
-            let parent = parent_vec.get(i).unwrap();
-            if parent.is_some() {
-                let object = out_objects.get_mut(i).unwrap();
-                object.parent_index = Some(parent.unwrap());
-            }
-        }
-    }
-
     // Convert object local matrix to world matrix
     let mut matrix_vec: Vec<[[f32; 4]; 4]> = Vec::with_capacity(out_objects.len());
     for object in &out_objects {
@@ -257,9 +686,215 @@ pub async fn load_gltf_scene(
         out_objects.get_mut(i).unwrap().world_transform = matrix_vec[i];
     }
 
+    // Load cameras
+    let mut out_cameras: Vec<engine::scene::SceneCamera> = Vec::new();
+    for (object_index, (node, _)) in reachable_nodes.iter().enumerate() {
+        let camera: gltf::Camera<'_> = match node.camera() {
+            Some(camera) => camera,
+            None => continue,
+        };
+        let projection: engine::scene::CameraProjection = match camera.projection() {
+            gltf::camera::Projection::Perspective(perspective) => {
+                engine::scene::CameraProjection::Perspective {
+                    yfov: perspective.yfov(),
+                    znear: perspective.znear(),
+                    zfar: perspective.zfar(),
+                }
+            }
+            gltf::camera::Projection::Orthographic(orthographic) => {
+                engine::scene::CameraProjection::Orthographic {
+                    ymag: orthographic.ymag(),
+                    znear: orthographic.znear(),
+                    zfar: orthographic.zfar(),
+                }
+            }
+        };
+
+        out_cameras.push(engine::scene::SceneCamera {
+            _name: camera.name().or(node.name()).map(|name| name.to_string()),
+            node: object_index as u32,
+            projection: projection,
+        });
+    }
+
+    // Load lights
+    let mut out_lights: Vec<engine::scene::SceneLight> = Vec::new();
+    for (object_index, (node, _)) in reachable_nodes.iter().enumerate() {
+        let light: gltf::khr_lights_punctual::Light<'_> = match node.light() {
+            Some(light) => light,
+            None => continue,
+        };
+        let mut scene_light = engine::scene::SceneLight {
+            _name: light.name().or(node.name()).map(|name| name.to_string()),
+            node: object_index as u32,
+            color: light.color(),
+            intensity: light.intensity(),
+            range: light.range(),
+            ..Default::default()
+        };
+        match light.kind() {
+            gltf::khr_lights_punctual::Kind::Directional => {
+                scene_light.kind = engine::scene::LightKind::Directional;
+            }
+            gltf::khr_lights_punctual::Kind::Point => {
+                scene_light.kind = engine::scene::LightKind::Point;
+            }
+            gltf::khr_lights_punctual::Kind::Spot {
+                inner_cone_angle,
+                outer_cone_angle,
+            } => {
+                scene_light.kind = engine::scene::LightKind::Spot;
+                scene_light.inner_cone_angle = inner_cone_angle;
+                scene_light.outer_cone_angle = outer_cone_angle;
+            }
+        }
+
+        out_lights.push(scene_light);
+    }
+
+    // Load skins
+    let mut out_skins: Vec<engine::scene::SceneSkin> = Vec::new();
+    for skin in gltf.skins() {
+        let reader = skin.reader(|buffer| Some(&buffer_data[buffer.index()]));
+        // joints must be in the same scene as the skinned node, strays fall back to the first object
+        let joints: Vec<u32> = skin
+            .joints()
+            .map(|joint| node_to_object[joint.index()].unwrap_or(0))
+            .collect::<Vec<_>>();
+        // identity matrices when inverseBindMatrices is undefined
+        let inverse_bind_matrices: Vec<[[f32; 4]; 4]> = match reader.read_inverse_bind_matrices() {
+            Some(iter) => iter.collect::<Vec<_>>(),
+            None => vec![glam::Mat4::IDENTITY.to_cols_array_2d(); joints.len()],
+        };
+
+        out_skins.push(engine::scene::SceneSkin {
+            _name: skin.name().map(|name| name.to_string()),
+            joints: joints,
+            inverse_bind_matrices: inverse_bind_matrices,
+        });
+    }
+
+    // Load animations
+    let mut out_animations: Vec<engine::animation::AnimationClip> = Vec::new();
+    for animation in gltf.animations() {
+        let mut clip = engine::animation::AnimationClip {
+            name: animation.name().map(|name| name.to_string()),
+            ..Default::default()
+        };
+
+        for (channel_index, channel) in animation.channels().enumerate() {
+            let reader = channel.reader(|buffer| Some(&buffer_data[buffer.index()]));
+            let times: Vec<f32> = match reader.read_inputs() {
+                Some(iter) => iter.collect::<Vec<_>>(),
+                None => continue,
+            };
+            let (property, values): (engine::animation::AnimationProperty, Vec<f32>) =
+                match reader.read_outputs() {
+                    Some(gltf::animation::util::ReadOutputs::Translations(iter)) => (
+                        engine::animation::AnimationProperty::Translation,
+                        iter.flatten().collect::<Vec<_>>(),
+                    ),
+                    Some(gltf::animation::util::ReadOutputs::Rotations(iter)) => (
+                        engine::animation::AnimationProperty::Rotation,
+                        iter.into_f32().flatten().collect::<Vec<_>>(),
+                    ),
+                    Some(gltf::animation::util::ReadOutputs::Scales(iter)) => (
+                        engine::animation::AnimationProperty::Scale,
+                        iter.flatten().collect::<Vec<_>>(),
+                    ),
+                    Some(gltf::animation::util::ReadOutputs::MorphTargetWeights(iter)) => (
+                        engine::animation::AnimationProperty::MorphWeights,
+                        iter.into_f32().collect::<Vec<_>>(),
+                    ),
+                    None => continue,
+                };
+            let interpolation: engine::animation::Interpolation = match channel
+                .sampler()
+                .interpolation()
+            {
+                gltf::animation::Interpolation::Step => engine::animation::Interpolation::Step,
+                gltf::animation::Interpolation::Linear => engine::animation::Interpolation::Linear,
+                gltf::animation::Interpolation::CubicSpline => {
+                    engine::animation::Interpolation::CubicSpline
+                }
+            };
+
+            // channels of nodes outside the scene are dropped
+            let target: u32 = match node_to_object[channel.target().node().index()] {
+                Some(object_index) => object_index,
+                None => continue,
+            };
+
+            // sampling indexes values per keyframe and divides by the time between keyframes
+            let component_count: usize = match property {
+                engine::animation::AnimationProperty::Translation => 3,
+                engine::animation::AnimationProperty::Rotation => 4,
+                engine::animation::AnimationProperty::Scale => 3,
+                engine::animation::AnimationProperty::MorphWeights => channel
+                    .target()
+                    .node()
+                    .mesh()
+                    .and_then(|mesh| {
+                        mesh.primitives()
+                            .map(|primitive| primitive.morph_targets().len())
+                            .max()
+                    })
+                    .unwrap_or(0),
+            };
+            let stride: usize = if interpolation == engine::animation::Interpolation::CubicSpline {
+                3 * component_count
+            } else {
+                component_count
+            };
+            let is_increasing: bool = times.windows(2).all(|pair| pair[0] < pair[1]);
+            if times.is_empty() || values.len() != times.len() * stride || !is_increasing {
+                out_warnings.push(LoadError::AnimationChannel {
+                    animation: animation.index(),
+                    channel: channel_index,
+                });
+                continue;
+            }
+            clip.duration = clip.duration.max(*times.last().unwrap_or(&0.0));
+            clip.channels.push(engine::animation::AnimationChannel {
+                target: target,
+                property: property,
+                interpolation: interpolation,
+                times: times,
+                values: values,
+            });
+
+            // Animated node and its descendants are transformed every frame
+            let mut dynamic_stack: Vec<u32> = vec![target];
+            while let Some(index) = dynamic_stack.pop() {
+                let object = out_objects.get_mut(index as usize).unwrap();
+                object.is_dynamic = true;
+                dynamic_stack.extend(object.child_index.iter());
+            }
+        }
+
+        out_animations.push(clip);
+    }
+
     // Load materials
     for material in gltf.materials() {
-        let scene_material = get_gltf_material(&material, &buffer_data, &folder_path).await;
+        let scene_material =
+            get_gltf_material(&material, &buffer_data, &folder_path, &mut out_warnings).await;
+        out_materials.push(scene_material);
+    }
+    // Default material for primitives without material, placed after all materials
+    let default_material: Option<gltf::Material<'_>> = gltf
+        .meshes()
+        .flat_map(|mesh| mesh.primitives())
+        .map(|primitive| primitive.material())
+        .find(|material| material.index().is_none());
+    if default_material.is_some() {
+        let scene_material = get_gltf_material(
+            &default_material.unwrap(),
+            &buffer_data,
+            &folder_path,
+            &mut out_warnings,
+        )
+        .await;
         out_materials.push(scene_material);
     }
 
@@ -272,60 +907,236 @@ pub async fn load_gltf_scene(
         out_materials.len()
     );
 
-    return (out_objects, out_materials);
+    return Ok(GltfScene {
+        objects: out_objects,
+        materials: out_materials,
+        skins: out_skins,
+        animations: out_animations,
+        cameras: out_cameras,
+        lights: out_lights,
+        scenes: gltf
+            .scenes()
+            .map(|scene| {
+                scene
+                    .name()
+                    .map_or(format!("scene {}", scene.index()), |name| name.to_string())
+            })
+            .collect::<Vec<_>>(),
+        active_scene: gltf_scene.as_ref().map(|scene| scene.index()),
+        warnings: out_warnings,
+    });
+}
+
+// Replace the scene contents with a .gltf, also used to switch between its scenes
+// Warnings are logged, a fatal error leaves the scene untouched for the caller to report
+pub async fn load_gltf_into_scene(
+    scene: &std::rc::Rc<std::cell::RefCell<engine::scene::Scene>>,
+    file_name: &str,
+    options: &LoadOptions,
+) -> Result<(), LoadError> {
+    let gltf_scene: GltfScene = load_gltf_scene(file_name, options).await?;
+    for warning in gltf_scene.warnings.iter() {
+        log::warn!("{}", warning);
+    }
+
+    let mut scene_value = scene.borrow_mut();
+    scene_value.gltf_path = file_name.to_string();
+    scene_value.load_options = *options;
+    scene_value.objects = gltf_scene.objects;
+    scene_value.batched_objects = Vec::new();
+    scene_value.materials = gltf_scene.materials;
+    scene_value.skins = gltf_scene.skins;
+    scene_value.animations = gltf_scene.animations;
+    scene_value.animation_player = Default::default();
+    if scene_value.animations.is_empty() == false {
+        scene_value.animation_player.clip_index = Some(0);
+        scene_value.animation_player.is_playing = true;
+    }
+    scene_value.cameras = gltf_scene.cameras;
+    if scene_value.cameras.is_empty() == false {
+        scene_value.set_active_camera(Some(0));
+    } else {
+        scene_value.set_active_camera(None);
+    }
+    scene_value.lights = gltf_scene.lights;
+    scene_value.scenes = gltf_scene.scenes;
+    scene_value.active_scene = gltf_scene.active_scene;
+    drop(scene_value);
+
+    // Batch objects
+    engine::scene::batch_objects(scene);
+    return Ok(());
+}
+
+fn read_accessor_items<T: gltf::accessor::Item + Copy, const N: usize>(
+    accessor: gltf::Accessor<'_>,
+    buffer_data: &Vec<Vec<u8>>,
+    to_f32: impl Fn(T) -> f32,
+) -> Vec<[f32; N]>
+where
+    [T; N]: gltf::accessor::Item,
+{
+    return gltf::accessor::Iter::<[T; N]>::new(accessor, |buffer| {
+        buffer_data.get(buffer.index()).map(|data| data.as_slice())
+    })
+    .map_or(Vec::new(), |iter| {
+        iter.map(|item| item.map(|component| to_f32(component)))
+            .collect::<Vec<_>>()
+    });
+}
+
+// Float attribute of any KHR_mesh_quantization component type, normalized integers map to [-1, 1] or [0, 1]
+fn read_accessor_f32<const N: usize>(
+    accessor: gltf::Accessor<'_>,
+    buffer_data: &Vec<Vec<u8>>,
+) -> Vec<[f32; N]>
+where
+    [f32; N]: gltf::accessor::Item,
+    [i8; N]: gltf::accessor::Item,
+    [u8; N]: gltf::accessor::Item,
+    [i16; N]: gltf::accessor::Item,
+    [u16; N]: gltf::accessor::Item,
+    [u32; N]: gltf::accessor::Item,
+{
+    let normalized: bool = accessor.normalized();
+    match accessor.data_type() {
+        gltf::accessor::DataType::F32 => read_accessor_items(accessor, buffer_data, |c: f32| c),
+        gltf::accessor::DataType::I8 => read_accessor_items(accessor, buffer_data, |c: i8| {
+            if normalized {
+                (c as f32 / 127.0).max(-1.0)
+            } else {
+                c as f32
+            }
+        }),
+        gltf::accessor::DataType::U8 => read_accessor_items(accessor, buffer_data, |c: u8| {
+            if normalized {
+                c as f32 / 255.0
+            } else {
+                c as f32
+            }
+        }),
+        gltf::accessor::DataType::I16 => read_accessor_items(accessor, buffer_data, |c: i16| {
+            if normalized {
+                (c as f32 / 32767.0).max(-1.0)
+            } else {
+                c as f32
+            }
+        }),
+        gltf::accessor::DataType::U16 => read_accessor_items(accessor, buffer_data, |c: u16| {
+            if normalized {
+                c as f32 / 65535.0
+            } else {
+                c as f32
+            }
+        }),
+        gltf::accessor::DataType::U32 => {
+            read_accessor_items(accessor, buffer_data, |c: u32| c as f32)
+        }
+    }
 }
 
 fn get_gltf_mesh_from_node(
     node: &gltf::Node<'_>,
     buffer_data: &Vec<Vec<u8>>,
+    default_material_index: u32,
+    options: &LoadOptions,
+    warnings: &mut Vec<LoadError>,
 ) -> rendering::common::Mesh {
     let mesh: gltf::Mesh<'_> = node.mesh().expect("Got mesh");
 
     let mut mesh_vertices: Vec<rendering::common::Vertex> = Vec::new();
     let mut mesh_indices: Vec<u32> = Vec::new();
-    let mut mesh_material: Option<u32> = None;
+    let mut mesh_submeshes: Vec<rendering::common::SubMesh> = Vec::new();
+    let mut mesh_morph_targets: Vec<rendering::common::MorphTarget> = Vec::new();
 
     for primitive in mesh.primitives() {
         let reader = primitive.reader(|buffer| Some(&buffer_data[buffer.index()]));
-        if reader.read_positions().is_none() {
-            continue;
-        }
+        let position_accessor: gltf::Accessor<'_> = match primitive.get(&gltf::Semantic::Positions)
+        {
+            Some(accessor) => accessor,
+            None => continue,
+        };
 
-        let mut positions: Vec<[f32; 3]> = Vec::<[f32; 3]>::new();
+        // KHR_mesh_quantization allows integer positions, normals, tangents and uvs
+        let positions: Vec<[f32; 3]> = read_accessor_f32(position_accessor, buffer_data);
         let mut normals: Vec<[f32; 3]> = Vec::<[f32; 3]>::new();
-        let mut colors: Vec<[f32; 3]> = Vec::<[f32; 3]>::new();
-        let mut uvs: Vec<(usize, [f32; 2])> = Vec::new();
+        let mut colors: Vec<[f32; 4]> = Vec::<[f32; 4]>::new();
+        let mut uvs: Vec<[f32; 2]> = Vec::<[f32; 2]>::new();
+        let mut uvs1: Vec<[f32; 2]> = Vec::<[f32; 2]>::new();
         let mut tangents: Vec<[f32; 4]> = Vec::<[f32; 4]>::new();
+        let mut joints: Vec<[u16; 4]> = Vec::<[u16; 4]>::new();
+        let mut weights: Vec<[f32; 4]> = Vec::<[f32; 4]>::new();
 
-        if reader.read_positions().is_some() {
-            positions = {
-                let iter = reader.read_positions().unwrap();
-                iter.collect::<Vec<_>>()
-            };
+        if let Some(accessor) = primitive.get(&gltf::Semantic::Normals) {
+            normals = read_accessor_f32(accessor, buffer_data);
         }
-        if reader.read_normals().is_some() {
-            normals = {
-                let iter = reader.read_normals().unwrap();
-                iter.collect::<Vec<_>>()
-            };
+        if primitive.get(&gltf::Semantic::Colors(0)).is_some() {
+            match reader.read_colors(0) {
+                Some(iter) => colors = iter.into_rgba_f32().collect::<Vec<_>>(),
+                None => warnings.push(LoadError::VertexAttribute {
+                    mesh: mesh.index(),
+                    primitive: primitive.index(),
+                    attribute: "COLOR_0",
+                }),
+            }
+        }
+        if let Some(accessor) = primitive.get(&gltf::Semantic::TexCoords(0)) {
+            uvs = read_accessor_f32(accessor, buffer_data);
         }
-        if reader.read_colors(0).is_some() {
-            colors = {
-                let iter = reader.read_colors(0).unwrap().into_rgb_f32();
+        if let Some(accessor) = primitive.get(&gltf::Semantic::TexCoords(1)) {
+            uvs1 = read_accessor_f32(accessor, buffer_data);
+        }
+        if let Some(accessor) = primitive.get(&gltf::Semantic::Tangents) {
+            tangents = read_accessor_f32(accessor, buffer_data);
+        }
+        if reader.read_joints(0).is_some() && reader.read_weights(0).is_some() {
+            joints = {
+                let iter = reader.read_joints(0).unwrap().into_u16();
                 iter.collect::<Vec<_>>()
             };
-        }
-        if reader.read_tex_coords(0).is_some() {
-            uvs = {
-                let iter = reader.read_tex_coords(0).unwrap().into_f32();
-                iter.enumerate().collect::<Vec<_>>()
+            weights = {
+                let iter = reader.read_weights(0).unwrap().into_f32();
+                iter.collect::<Vec<_>>()
             };
         }
-        if reader.read_tangents().is_some() {
-            tangents = {
-                let iter = reader.read_tangents().unwrap();
-                iter.collect::<Vec<_>>()
+
+        // a short or long attribute falls back to its default instead of indexing out of range
+        let mut check_count = |is_valid: bool, attribute: &'static str| -> bool {
+            if !is_valid {
+                warnings.push(LoadError::VertexAttribute {
+                    mesh: mesh.index(),
+                    primitive: primitive.index(),
+                    attribute: attribute,
+                });
             }
+            return is_valid;
+        };
+        let vertex_count: usize = positions.len();
+        if !check_count(
+            normals.len() == vertex_count || normals.is_empty(),
+            "NORMAL",
+        ) {
+            normals.clear();
+        }
+        if !check_count(colors.len() == vertex_count || colors.is_empty(), "COLOR_0") {
+            colors.clear();
+        }
+        if !check_count(uvs.len() == vertex_count || uvs.is_empty(), "TEXCOORD_0") {
+            uvs.clear();
+        }
+        if !check_count(uvs1.len() == vertex_count || uvs1.is_empty(), "TEXCOORD_1") {
+            uvs1.clear();
+        }
+        if !check_count(
+            tangents.len() == vertex_count || tangents.is_empty(),
+            "TANGENT",
+        ) {
+            tangents.clear();
+        }
+        let is_skin_valid: bool = joints.len() == vertex_count && weights.len() == vertex_count;
+        if !check_count(is_skin_valid || joints.is_empty(), "JOINTS_0/WEIGHTS_0") {
+            joints.clear();
+            weights.clear();
         }
 
         let mut vertices: Vec<rendering::common::Vertex> = Vec::new();
@@ -336,37 +1147,191 @@ fn get_gltf_mesh_from_node(
                 } else {
                     [0.0, 0.0, 0.0, 1.0]
                 },
+                // missing COLOR_0 leaves the base color untouched
                 color: if colors.len() > 0 {
                     colors[i]
                 } else {
-                    [0.0, 0.0, 0.0]
+                    [1.0, 1.0, 1.0, 1.0]
                 },
-                uv: if uvs.len() > 0 { uvs[i].1 } else { [0.0, 0.0] },
+                uv: if uvs.len() > 0 { uvs[i] } else { [0.0, 0.0] },
                 normal: if normals.len() > 0 {
                     normals[i]
                 } else {
                     [0.0, 0.0, 1.0]
                 },
                 tangent: if tangents.len() > 0 {
-                    [tangents[i][0], tangents[i][1], tangents[i][2]]
+                    tangents[i]
+                } else {
+                    [1.0, 0.0, 0.0, 1.0]
+                },
+                joints: if joints.len() > 0 {
+                    joints[i].map(|joint| joint as u32)
+                } else {
+                    [0, 0, 0, 0]
+                },
+                weights: if weights.len() > 0 {
+                    weights[i]
                 } else {
-                    [0.0, 1.0, 0.0]
+                    [0.0, 0.0, 0.0, 0.0]
                 },
+                uv1: if uvs1.len() > 0 { uvs1[i] } else { [0.0, 0.0] },
             });
         }
 
-        let mut indices = {
-            let iter = reader.read_indices().unwrap().into_u32();
-            iter.collect::<Vec<_>>()
+        // non-indexed primitives draw their vertices in order
+        let indices: Vec<u32> = match reader.read_indices() {
+            Some(iter) => iter.into_u32().collect::<Vec<_>>(),
+            None => (0..vertices.len() as u32).collect::<Vec<_>>(),
         };
 
+        // strips, fans and loops are converted to lists
+        let (topology, mut indices): (rendering::common::Topology, Vec<u32>) =
+            match primitive.mode() {
+                gltf::mesh::Mode::Triangles => (rendering::common::Topology::Triangles, indices),
+                gltf::mesh::Mode::TriangleStrip => (
+                    rendering::common::Topology::Triangles,
+                    engine::geometry::triangle_strip_to_list(&indices),
+                ),
+                gltf::mesh::Mode::TriangleFan => (
+                    rendering::common::Topology::Triangles,
+                    engine::geometry::triangle_fan_to_list(&indices),
+                ),
+                gltf::mesh::Mode::Lines => (rendering::common::Topology::Lines, indices),
+                gltf::mesh::Mode::LineStrip => (
+                    rendering::common::Topology::Lines,
+                    engine::geometry::line_strip_to_list(&indices, false),
+                ),
+                gltf::mesh::Mode::LineLoop => (
+                    rendering::common::Topology::Lines,
+                    engine::geometry::line_strip_to_list(&indices, true),
+                ),
+                gltf::mesh::Mode::Points => (rendering::common::Topology::Points, indices),
+            };
+        // checked once here, the normal and tangent generators index vertices directly
+        if indices
+            .iter()
+            .any(|index| *index as usize >= vertices.len())
+        {
+            warnings.push(LoadError::IndexRange {
+                mesh: mesh.index(),
+                primitive: primitive.index(),
+            });
+            continue;
+        }
+        let is_triangles: bool = topology == rendering::common::Topology::Triangles;
+
+        let mut morph_targets: Vec<rendering::common::MorphTarget> = Vec::new();
+        for target in primitive.morph_targets() {
+            // missing attributes do not move
+            morph_targets.push(rendering::common::MorphTarget {
+                positions: target
+                    .positions()
+                    .map_or(vec![[0.0; 3]; vertices.len()], |accessor| {
+                        read_accessor_f32(accessor, buffer_data)
+                    }),
+                normals: target
+                    .normals()
+                    .map_or(vec![[0.0; 3]; vertices.len()], |accessor| {
+                        read_accessor_f32(accessor, buffer_data)
+                    }),
+                tangents: target
+                    .tangents()
+                    .map_or(vec![[0.0; 3]; vertices.len()], |accessor| {
+                        read_accessor_f32(accessor, buffer_data)
+                    }),
+            });
+        }
+        // the unweld remap and the mesh layout index deltas per vertex
+        for (i, target) in morph_targets.iter_mut().enumerate() {
+            if target.positions.len() != vertices.len()
+                || target.normals.len() != vertices.len()
+                || target.tangents.len() != vertices.len()
+            {
+                warnings.push(LoadError::MorphTarget {
+                    mesh: mesh.index(),
+                    primitive: primitive.index(),
+                    target: i,
+                });
+                target.positions.resize(vertices.len(), [0.0; 3]);
+                target.normals.resize(vertices.len(), [0.0; 3]);
+                target.tangents.resize(vertices.len(), [0.0; 3]);
+            }
+        }
+
+        // normals are generated before tangents, MikkTSpace depends on them
+        if normals.is_empty() && is_triangles {
+            // generated normals unweld vertices, morph deltas follow the source vertex
+            let source_indices: Vec<u32> = indices.clone();
+            match options.normal_smoothing_angle {
+                Some(angle) => {
+                    engine::geometry::generate_smooth_normals(&mut vertices, &mut indices, angle)
+                }
+                None => engine::geometry::generate_flat_normals(&mut vertices, &mut indices),
+            }
+            for target in morph_targets.iter_mut() {
+                target.positions = source_indices
+                    .iter()
+                    .map(|index| target.positions[*index as usize])
+                    .collect::<Vec<_>>();
+                target.normals = source_indices
+                    .iter()
+                    .map(|index| target.normals[*index as usize])
+                    .collect::<Vec<_>>();
+                target.tangents = source_indices
+                    .iter()
+                    .map(|index| target.tangents[*index as usize])
+                    .collect::<Vec<_>>();
+            }
+        }
+
+        // tangents follow MikkTSpace when the asset does not provide them
+        if tangents.is_empty() && uvs.len() > 0 && is_triangles {
+            if engine::geometry::generate_tangents(&mut vertices, &indices) == false {
+                warnings.push(LoadError::TangentGeneration {
+                    mesh: mesh.index(),
+                    primitive: primitive.index(),
+                });
+            }
+        }
+
+        // indices are relative to this primitive's vertices
+        let base_vertex: u32 = mesh_vertices.len() as u32;
+        for index in indices.iter_mut() {
+            *index += base_vertex;
+        }
+
+        mesh_submeshes.push(rendering::common::SubMesh {
+            index_offset: mesh_indices.len() as u32,
+            index_count: indices.len() as u32,
+            material: Some(
+                primitive
+                    .material()
+                    .index()
+                    .map_or(default_material_index, |index| index as u32),
+            ),
+            topology: topology,
+        });
+        // primitives share the target count, deltas are laid out like mesh_vertices
+        if mesh_morph_targets.len() < morph_targets.len() {
+            mesh_morph_targets.resize(morph_targets.len(), Default::default());
+        }
+        for (i, mesh_target) in mesh_morph_targets.iter_mut().enumerate() {
+            let end_vertex: usize = base_vertex as usize + vertices.len();
+            mesh_target.positions.resize(base_vertex as usize, [0.0; 3]);
+            mesh_target.normals.resize(base_vertex as usize, [0.0; 3]);
+            mesh_target.tangents.resize(base_vertex as usize, [0.0; 3]);
+            if let Some(target) = morph_targets.get(i) {
+                mesh_target.positions.extend_from_slice(&target.positions);
+                mesh_target.normals.extend_from_slice(&target.normals);
+                mesh_target.tangents.extend_from_slice(&target.tangents);
+            }
+            mesh_target.positions.resize(end_vertex, [0.0; 3]);
+            mesh_target.normals.resize(end_vertex, [0.0; 3]);
+            mesh_target.tangents.resize(end_vertex, [0.0; 3]);
+        }
+
         mesh_vertices.append(&mut vertices);
         mesh_indices.append(&mut indices);
-        mesh_material = if primitive.material().index().is_some() {
-            Some(primitive.material().index().unwrap() as u32)
-        } else {
-            mesh_material
-        };
 
         /*
         log::debug!(
@@ -376,69 +1341,87 @@ fn get_gltf_mesh_from_node(
         );*/
     }
 
+    // instanced meshes are read once per node, warn once per mesh
+    let is_reported: bool = warnings
+        .iter()
+        .any(|warning| matches!(warning, LoadError::UnnamedMesh(index) if *index == mesh.index()));
+    if mesh.name().is_none() && !is_reported {
+        warnings.push(LoadError::UnnamedMesh(mesh.index()));
+    }
+
     rendering::common::Mesh {
-        _name: mesh.name().unwrap().to_string(),
+        _name: mesh
+            .name()
+            .map_or(format!("mesh_{}", mesh.index()), |name| name.to_string()),
         vertices: mesh_vertices,
         indices: mesh_indices,
-        material: mesh_material,
+        submeshes: mesh_submeshes,
+        morph_targets: mesh_morph_targets,
     }
 }
 
 async fn get_gltf_material<'a>(
     material: &gltf::Material<'a>,
     buffer_data: &Vec<Vec<u8>>,
-    gltf_folder_path: &str
-) -> engine::scene::SceneMaterial{
-
+    gltf_folder_path: &str,
+    warnings: &mut Vec<LoadError>,
+) -> engine::scene::SceneMaterial {
     let pbr = material.pbr_metallic_roughness();
+    let mut base_color_factor: [f32; 4] = pbr.base_color_factor();
 
     // base color
     let mut base_color_texture_data: Vec<u8> = Vec::new();
     let mut base_color_texture_size: [u32; 2] = [1, 1];
+    let mut base_color_sampler: engine::scene::SceneSampler = Default::default();
+    let mut base_color_transform: engine::scene::SceneTextureTransform = Default::default();
     {
         if pbr.base_color_texture().is_some() {
+            let base_color_info = pbr
+                .base_color_texture()
+                .expect("Should have base color texture");
+            base_color_sampler = get_gltf_sampler(&base_color_info.texture().sampler());
+            base_color_transform = get_gltf_texture_transform(
+                base_color_info.tex_coord(),
+                base_color_info.extension_value("KHR_texture_transform"),
+            );
             let base_color_texture_source = &pbr
                 .base_color_texture()
                 .map(|tex| tex.texture().source().source())
                 .expect("texture");
 
-            match base_color_texture_source {
-                gltf::image::Source::View { view, mime_type: _ } => {
-                    // embedded data is yet
-                    base_color_texture_data = buffer_data[view.buffer().index()].clone();
-                }
-                gltf::image::Source::Uri { uri, mime_type: _ } => {
-                    // from url
-                    let texture_path = gltf_folder_path.to_string() + uri;
-                    (base_color_texture_data, base_color_texture_size) =
-                        extract_texture_data(&texture_path).await;
-                }
-            };
+            match get_gltf_texture_data(base_color_texture_source, buffer_data, gltf_folder_path)
+                .await
+            {
+                Ok(texture) => (base_color_texture_data, base_color_texture_size) = texture,
+                Err(error) => warnings.push(error),
+            }
         }
     }
 
     // normal map
     let mut normal_texture_data: Vec<u8> = Vec::new();
     let mut normal_texture_size: [u32; 2] = [1, 1];
+    let mut normal_sampler: engine::scene::SceneSampler = Default::default();
+    let mut normal_transform: engine::scene::SceneTextureTransform = Default::default();
     {
         if material.normal_texture().is_some() {
+            let normal_info = material
+                .normal_texture()
+                .expect("Should have normal texture");
+            normal_sampler = get_gltf_sampler(&normal_info.texture().sampler());
+            normal_transform = get_gltf_texture_transform(
+                normal_info.tex_coord(),
+                normal_info.extension_value("KHR_texture_transform"),
+            );
             let normal_source = &material
                 .normal_texture()
                 .expect("Should have normal texture")
                 .texture()
                 .source()
                 .source();
-            match normal_source {
-                gltf::image::Source::View { view, mime_type: _ } => {
-                    // embedded data is yet
-                    normal_texture_data = buffer_data[view.buffer().index()].clone();
-                }
-                gltf::image::Source::Uri { uri, mime_type: _ } => {
-                    // from url
-                    let texture_path = gltf_folder_path.to_string() + uri;
-                    (normal_texture_data, normal_texture_size) =
-                        extract_texture_data(&texture_path).await;
-                }
+            match get_gltf_texture_data(normal_source, buffer_data, gltf_folder_path).await {
+                Ok(texture) => (normal_texture_data, normal_texture_size) = texture,
+                Err(error) => warnings.push(error),
             }
         }
     }
@@ -446,76 +1429,536 @@ async fn get_gltf_material<'a>(
     // metalic roughness texture
     let mut metal_texture_data: Vec<u8> = Vec::new();
     let mut metal_texture_size: [u32; 2] = [1, 1];
+    let mut metal_sampler: engine::scene::SceneSampler = Default::default();
+    let mut metal_transform: engine::scene::SceneTextureTransform = Default::default();
     {
         if pbr.metallic_roughness_texture().is_some() {
+            let metal_info = pbr
+                .metallic_roughness_texture()
+                .expect("Should have metallic roughness texture");
+            metal_sampler = get_gltf_sampler(&metal_info.texture().sampler());
+            metal_transform = get_gltf_texture_transform(
+                metal_info.tex_coord(),
+                metal_info.extension_value("KHR_texture_transform"),
+            );
             let metal_texture_source = &pbr
                 .metallic_roughness_texture()
                 .map(|tex| tex.texture().source().source())
                 .expect("texture");
 
-            match metal_texture_source {
-                gltf::image::Source::View { view, mime_type: _ } => {
-                    // embedded data is yet
-                    metal_texture_data = buffer_data[view.buffer().index()].clone();
-                }
-                gltf::image::Source::Uri { uri, mime_type: _ } => {
-                    // from url
-                    let texture_path = gltf_folder_path.to_string() + uri;
-                    (metal_texture_data, metal_texture_size) =
-                        extract_texture_data(&texture_path).await;
-                }
-            };
+            match get_gltf_texture_data(metal_texture_source, buffer_data, gltf_folder_path).await {
+                Ok(texture) => (metal_texture_data, metal_texture_size) = texture,
+                Err(error) => warnings.push(error),
+            }
+        }
+    }
+
+    // emissive texture
+    let mut emissive_texture_data: Vec<u8> = Vec::new();
+    let mut emissive_texture_size: [u32; 2] = [1, 1];
+    let mut emissive_sampler: engine::scene::SceneSampler = Default::default();
+    let mut emissive_transform: engine::scene::SceneTextureTransform = Default::default();
+    {
+        if material.emissive_texture().is_some() {
+            let emissive_info = material
+                .emissive_texture()
+                .expect("Should have emissive texture");
+            emissive_sampler = get_gltf_sampler(&emissive_info.texture().sampler());
+            emissive_transform = get_gltf_texture_transform(
+                emissive_info.tex_coord(),
+                emissive_info.extension_value("KHR_texture_transform"),
+            );
+            let emissive_texture_source = &material
+                .emissive_texture()
+                .map(|tex| tex.texture().source().source())
+                .expect("texture");
+
+            match get_gltf_texture_data(emissive_texture_source, buffer_data, gltf_folder_path)
+                .await
+            {
+                Ok(texture) => (emissive_texture_data, emissive_texture_size) = texture,
+                Err(error) => warnings.push(error),
+            }
+        }
+    }
+
+    // occlusion texture
+    let mut occlusion_texture_data: Vec<u8> = Vec::new();
+    let mut occlusion_texture_size: [u32; 2] = [1, 1];
+    let mut occlusion_strength: f32 = 1.0;
+    let mut occlusion_sampler: engine::scene::SceneSampler = Default::default();
+    let mut occlusion_transform: engine::scene::SceneTextureTransform = Default::default();
+    {
+        if material.occlusion_texture().is_some() {
+            let occlusion_texture = material
+                .occlusion_texture()
+                .expect("Should have occlusion texture");
+            occlusion_strength = occlusion_texture.strength();
+            occlusion_sampler = get_gltf_sampler(&occlusion_texture.texture().sampler());
+            occlusion_transform = get_gltf_texture_transform(
+                occlusion_texture.tex_coord(),
+                occlusion_texture.extension_value("KHR_texture_transform"),
+            );
+
+            let occlusion_texture_source = &occlusion_texture.texture().source().source();
+            match get_gltf_texture_data(occlusion_texture_source, buffer_data, gltf_folder_path)
+                .await
+            {
+                Ok(texture) => (occlusion_texture_data, occlusion_texture_size) = texture,
+                Err(error) => warnings.push(error),
+            }
         }
     }
 
-    // KHR_materials_pbrSpecularGlossiness
+    // KHR_materials_pbrSpecularGlossiness, converted to metallic roughness
+    let mut metallic_factor: f32 = pbr.metallic_factor();
+    let mut roughness_factor: f32 = pbr.roughness_factor();
     let pbr_specular_glossiness = material.pbr_specular_glossiness();
     if pbr_specular_glossiness.is_some() {
         let pbr_specular_glossiness =
             pbr_specular_glossiness.expect("Should have specular glossiness");
+        // the extension replaces the metallic roughness inputs
+        base_color_texture_data = Vec::new();
+        base_color_texture_size = [1, 1];
+        metal_texture_data = Vec::new();
+        metal_texture_size = [1, 1];
+
         // diffuse texture
         {
             if pbr_specular_glossiness.diffuse_texture().is_some() {
+                let diffuse_info = pbr_specular_glossiness
+                    .diffuse_texture()
+                    .expect("Should have diffuse texture");
+                base_color_sampler = get_gltf_sampler(&diffuse_info.texture().sampler());
+                base_color_transform = get_gltf_texture_transform(
+                    diffuse_info.tex_coord(),
+                    diffuse_info.extension_value("KHR_texture_transform"),
+                );
                 let diffuse_texture_source = &pbr_specular_glossiness
                     .diffuse_texture()
                     .map(|tex| tex.texture().source().source())
                     .expect("Should have diffuse texture");
 
-                match diffuse_texture_source {
-                    gltf::image::Source::View { view, mime_type: _ } => {
-                        // embedded data is yet
-                        base_color_texture_data = buffer_data[view.buffer().index()].clone();
-                    }
-                    gltf::image::Source::Uri { uri, mime_type: _ } => {
-                        // from url
-                        let texture_path = gltf_folder_path.to_string() + uri;
-                        (base_color_texture_data, base_color_texture_size) =
-                            extract_texture_data(&texture_path).await;
+                match get_gltf_texture_data(diffuse_texture_source, buffer_data, gltf_folder_path)
+                    .await
+                {
+                    Ok(texture) => (base_color_texture_data, base_color_texture_size) = texture,
+                    Err(error) => warnings.push(error),
+                }
+            }
+        }
+
+        // specular glossiness texture
+        let mut specular_glossiness_texture_data: Vec<u8> = Vec::new();
+        let mut specular_glossiness_texture_size: [u32; 2] = [1, 1];
+        let mut is_same_layout: bool = true;
+        {
+            if pbr_specular_glossiness
+                .specular_glossiness_texture()
+                .is_some()
+            {
+                let specular_glossiness_info = pbr_specular_glossiness
+                    .specular_glossiness_texture()
+                    .expect("Should have specular glossiness texture");
+                let specular_glossiness_sampler: engine::scene::SceneSampler =
+                    get_gltf_sampler(&specular_glossiness_info.texture().sampler());
+                let specular_glossiness_transform: engine::scene::SceneTextureTransform =
+                    get_gltf_texture_transform(
+                        specular_glossiness_info.tex_coord(),
+                        specular_glossiness_info.extension_value("KHR_texture_transform"),
+                    );
+                // converted textures share the diffuse uv layout when there is one
+                if base_color_texture_data.is_empty() {
+                    base_color_sampler = specular_glossiness_sampler;
+                    base_color_transform = specular_glossiness_transform;
+                } else {
+                    is_same_layout = specular_glossiness_sampler == base_color_sampler
+                        && specular_glossiness_transform == base_color_transform;
+                }
+                let specular_glossiness_source =
+                    &specular_glossiness_info.texture().source().source();
+
+                match get_gltf_texture_data(
+                    specular_glossiness_source,
+                    buffer_data,
+                    gltf_folder_path,
+                )
+                .await
+                {
+                    Ok(texture) => {
+                        (
+                            specular_glossiness_texture_data,
+                            specular_glossiness_texture_size,
+                        ) = texture
                     }
-                };
+                    Err(error) => warnings.push(error),
+                }
+            }
+        }
+
+        let diffuse_factor: [f32; 4] = pbr_specular_glossiness.diffuse_factor();
+        let specular_factor: [f32; 3] = pbr_specular_glossiness.specular_factor();
+        let glossiness_factor: f32 = pbr_specular_glossiness.glossiness_factor();
+        if base_color_texture_data.is_empty() && specular_glossiness_texture_data.is_empty() {
+            let (base_color, metallic, roughness) =
+                convert_specular_glossiness(diffuse_factor, specular_factor, glossiness_factor);
+            base_color_factor = base_color;
+            metallic_factor = metallic;
+            roughness_factor = roughness;
+        } else {
+            // texels are paired by normalized uv, which only holds for a shared layout
+            if let (false, Some(index)) = (is_same_layout, material.index()) {
+                warnings.push(LoadError::SpecularGlossinessLayout(index));
+            }
+            // factors are baked into the converted textures
+            (
+                (base_color_texture_data, base_color_texture_size),
+                (metal_texture_data, metal_texture_size),
+            ) = convert_specular_glossiness_textures(
+                (&base_color_texture_data, base_color_texture_size),
+                (
+                    &specular_glossiness_texture_data,
+                    specular_glossiness_texture_size,
+                ),
+                diffuse_factor,
+                specular_factor,
+                glossiness_factor,
+            );
+            metal_sampler = base_color_sampler;
+            metal_transform = base_color_transform;
+            base_color_factor = [1.0, 1.0, 1.0, 1.0];
+            metallic_factor = 1.0;
+            roughness_factor = 1.0;
+        }
+    }
+
+    // KHR_materials_specular, KHR_materials_clearcoat and KHR_materials_sheen
+    let get_factor = |extension: Option<&gltf::json::Value>, key: &str, default: f32| -> f32 {
+        extension
+            .and_then(|value| value.get(key)?.as_f64())
+            .map_or(default, |value| value as f32)
+    };
+    let get_color =
+        |extension: Option<&gltf::json::Value>, key: &str, default: [f32; 3]| -> [f32; 3] {
+            match extension.and_then(|value| value.get(key)?.as_array()) {
+                Some(values) if values.len() == 3 => {
+                    [0, 1, 2].map(|i| values[i].as_f64().unwrap_or(0.0) as f32)
+                }
+                _ => default,
             }
+        };
+    let specular: Option<&gltf::json::Value> = material.extension_value("KHR_materials_specular");
+    let clearcoat: Option<&gltf::json::Value> = material.extension_value("KHR_materials_clearcoat");
+    let sheen: Option<&gltf::json::Value> = material.extension_value("KHR_materials_sheen");
+    // only the factors of these extensions are supported, their textures (clearcoatTexture,
+    // clearcoatNormalTexture, sheenColorTexture, specularColorTexture...) are reported and skipped
+    for (extension, value) in [
+        ("KHR_materials_specular", specular),
+        ("KHR_materials_clearcoat", clearcoat),
+        ("KHR_materials_sheen", sheen),
+    ] {
+        let has_texture: bool = value
+            .and_then(|value| value.as_object())
+            .map_or(false, |object| {
+                object.keys().any(|key| key.ends_with("Texture"))
+            });
+        if let (true, Some(index)) = (has_texture, material.index()) {
+            warnings.push(LoadError::ExtensionTexture {
+                material: index,
+                extension: extension,
+            });
         }
     }
 
-    // empty texture
+    // empty texture, white so that the material factors are used as is
     if base_color_texture_data.is_empty() {
-        base_color_texture_data = [255, 0, 255, 255].to_vec();
+        base_color_texture_data = [255, 255, 255, 255].to_vec();
     }
     if normal_texture_data.is_empty() {
         normal_texture_data = [128, 128, 255, 255].to_vec();
     }
-    if metal_texture_data.is_empty(){
-        metal_texture_data = [0, 0, 0, 255].to_vec();
+    if metal_texture_data.is_empty() {
+        metal_texture_data = [255, 255, 255, 255].to_vec();
+    }
+    if emissive_texture_data.is_empty() {
+        emissive_texture_data = [255, 255, 255, 255].to_vec();
+    }
+    if occlusion_texture_data.is_empty() {
+        occlusion_texture_data = [255, 255, 255, 255].to_vec();
+    }
+
+    if let (None, Some(index)) = (material.name(), material.index()) {
+        warnings.push(LoadError::UnnamedMaterial(index));
     }
 
     engine::scene::SceneMaterial {
-        _name: Some(material.name().unwrap().to_string()),
+        _name: material.name().map(|name| name.to_string()),
         base_color_texture: base_color_texture_data,
         base_color_texture_size: base_color_texture_size,
         normal_texture: normal_texture_data,
         normal_texture_size: normal_texture_size,
         metallic_roughness_texture: metal_texture_data,
         metallic_roughness_texture_size: metal_texture_size,
-        ..Default::default()
+        emissive_texture: emissive_texture_data,
+        emissive_texture_size: emissive_texture_size,
+        occlusion_texture: occlusion_texture_data,
+        occlusion_texture_size: occlusion_texture_size,
+        base_color_sampler: base_color_sampler,
+        normal_sampler: normal_sampler,
+        metallic_roughness_sampler: metal_sampler,
+        emissive_sampler: emissive_sampler,
+        occlusion_sampler: occlusion_sampler,
+        base_color_transform: base_color_transform,
+        normal_transform: normal_transform,
+        metallic_roughness_transform: metal_transform,
+        emissive_transform: emissive_transform,
+        occlusion_transform: occlusion_transform,
+        base_color_factor: base_color_factor,
+        metallic_factor: metallic_factor,
+        roughness_factor: roughness_factor,
+        emissive_factor: material.emissive_factor(),
+        occlusion_strength: occlusion_strength,
+        alpha_mode: match material.alpha_mode() {
+            gltf::material::AlphaMode::Opaque => engine::scene::AlphaMode::Opaque,
+            gltf::material::AlphaMode::Mask => engine::scene::AlphaMode::Mask,
+            gltf::material::AlphaMode::Blend => engine::scene::AlphaMode::Blend,
+        },
+        alpha_cutoff: material.alpha_cutoff().unwrap_or(0.5),
+        double_sided: material.double_sided(),
+        unlit: material.unlit(),
+        specular_factor: get_factor(specular, "specularFactor", 1.0),
+        specular_color_factor: get_color(specular, "specularColorFactor", [1.0, 1.0, 1.0]),
+        clearcoat_factor: get_factor(clearcoat, "clearcoatFactor", 0.0),
+        clearcoat_roughness_factor: get_factor(clearcoat, "clearcoatRoughnessFactor", 0.0),
+        sheen_color_factor: get_color(sheen, "sheenColorFactor", [0.0, 0.0, 0.0]),
+        sheen_roughness_factor: get_factor(sheen, "sheenRoughnessFactor", 0.0),
+    }
+}
+
+// KHR_materials_pbrSpecularGlossiness
+
+const DIELECTRIC_SPECULAR: f32 = 0.04;
+
+fn get_perceived_brightness(color: [f32; 3]) -> f32 {
+    return (0.299 * color[0] * color[0]
+        + 0.587 * color[1] * color[1]
+        + 0.114 * color[2] * color[2])
+        .sqrt();
+}
+
+// Metalness that reproduces both the diffuse and the specular brightness
+fn solve_metallic(diffuse: f32, specular: f32, one_minus_specular_strength: f32) -> f32 {
+    if specular < DIELECTRIC_SPECULAR {
+        return 0.0;
+    }
+
+    let a: f32 = DIELECTRIC_SPECULAR;
+    let b: f32 = diffuse * one_minus_specular_strength / (1.0 - DIELECTRIC_SPECULAR) + specular
+        - 2.0 * DIELECTRIC_SPECULAR;
+    let c: f32 = DIELECTRIC_SPECULAR - specular;
+    let discriminant: f32 = (b * b - 4.0 * a * c).max(0.0);
+
+    return ((-b + discriminant.sqrt()) / (2.0 * a)).clamp(0.0, 1.0);
+}
+
+// Linear diffuse, specular and glossiness to linear base color, metallic and roughness
+fn convert_specular_glossiness(
+    diffuse: [f32; 4],
+    specular: [f32; 3],
+    glossiness: f32,
+) -> ([f32; 4], f32, f32) {
+    let one_minus_specular_strength: f32 = 1.0 - specular[0].max(specular[1]).max(specular[2]);
+    let metallic: f32 = solve_metallic(
+        get_perceived_brightness([diffuse[0], diffuse[1], diffuse[2]]),
+        get_perceived_brightness(specular),
+        one_minus_specular_strength,
+    );
+
+    // blend the base color seen through the diffuse and the specular
+    let mut base_color: [f32; 4] = [0.0, 0.0, 0.0, diffuse[3]];
+    for i in 0..3 {
+        let from_diffuse: f32 = diffuse[i] * one_minus_specular_strength
+            / (1.0 - DIELECTRIC_SPECULAR)
+            / (1.0 - metallic).max(f32::EPSILON);
+        let from_specular: f32 =
+            (specular[i] - DIELECTRIC_SPECULAR * (1.0 - metallic)) / metallic.max(f32::EPSILON);
+        base_color[i] =
+            (from_diffuse + (from_specular - from_diffuse) * metallic * metallic).clamp(0.0, 1.0);
+    }
+
+    return (base_color, metallic, 1.0 - glossiness);
+}
+
+fn srgb_to_linear(value: f32) -> f32 {
+    if value <= 0.04045 {
+        return value / 12.92;
+    }
+    return ((value + 0.055) / 1.055).powf(2.4);
+}
+
+fn linear_to_srgb(value: f32) -> f32 {
+    if value <= 0.0031308 {
+        return value * 12.92;
+    }
+    return 1.055 * value.powf(1.0 / 2.4) - 0.055;
+}
+
+// Per texel conversion into a base color (sRGB) and a metallic roughness texture (linear),
+// at the larger of both sizes, the other texture is sampled nearest
+fn convert_specular_glossiness_textures(
+    diffuse_texture: (&[u8], [u32; 2]),
+    specular_glossiness_texture: (&[u8], [u32; 2]),
+    diffuse_factor: [f32; 4],
+    specular_factor: [f32; 3],
+    glossiness_factor: f32,
+) -> ((Vec<u8>, [u32; 2]), (Vec<u8>, [u32; 2])) {
+    let white: [u8; 4] = [255, 255, 255, 255];
+    let get_texel = |texture: (&[u8], [u32; 2]), u: f32, v: f32| -> [u8; 4] {
+        if texture.0.is_empty() {
+            return white;
+        }
+        let x: usize = ((u * texture.1[0] as f32) as usize).min(texture.1[0] as usize - 1);
+        let y: usize = ((v * texture.1[1] as f32) as usize).min(texture.1[1] as usize - 1);
+        let offset: usize = (y * texture.1[0] as usize + x) * 4;
+        return [
+            texture.0[offset],
+            texture.0[offset + 1],
+            texture.0[offset + 2],
+            texture.0[offset + 3],
+        ];
+    };
+    let srgb_table: Vec<f32> = (0..256)
+        .map(|value| srgb_to_linear(value as f32 / 255.0))
+        .collect::<Vec<_>>();
+
+    let size: [u32; 2] = [
+        diffuse_texture.1[0].max(specular_glossiness_texture.1[0]),
+        diffuse_texture.1[1].max(specular_glossiness_texture.1[1]),
+    ];
+    let texel_count: usize = (size[0] * size[1]) as usize;
+    let mut base_color_data: Vec<u8> = Vec::with_capacity(texel_count * 4);
+    let mut metallic_roughness_data: Vec<u8> = Vec::with_capacity(texel_count * 4);
+    for y in 0..size[1] {
+        for x in 0..size[0] {
+            let u: f32 = (x as f32 + 0.5) / size[0] as f32;
+            let v: f32 = (y as f32 + 0.5) / size[1] as f32;
+            let diffuse_texel: [u8; 4] = get_texel(diffuse_texture, u, v);
+            let specular_glossiness_texel: [u8; 4] = get_texel(specular_glossiness_texture, u, v);
+
+            // color channels are sRGB, glossiness and alpha are linear
+            let diffuse: [f32; 4] = [
+                srgb_table[diffuse_texel[0] as usize] * diffuse_factor[0],
+                srgb_table[diffuse_texel[1] as usize] * diffuse_factor[1],
+                srgb_table[diffuse_texel[2] as usize] * diffuse_factor[2],
+                diffuse_texel[3] as f32 / 255.0 * diffuse_factor[3],
+            ];
+            let specular: [f32; 3] = [
+                srgb_table[specular_glossiness_texel[0] as usize] * specular_factor[0],
+                srgb_table[specular_glossiness_texel[1] as usize] * specular_factor[1],
+                srgb_table[specular_glossiness_texel[2] as usize] * specular_factor[2],
+            ];
+            let glossiness: f32 = specular_glossiness_texel[3] as f32 / 255.0 * glossiness_factor;
+
+            let (base_color, metallic, roughness) =
+                convert_specular_glossiness(diffuse, specular, glossiness);
+            base_color_data.extend_from_slice(&[
+                (linear_to_srgb(base_color[0]) * 255.0).round() as u8,
+                (linear_to_srgb(base_color[1]) * 255.0).round() as u8,
+                (linear_to_srgb(base_color[2]) * 255.0).round() as u8,
+                (base_color[3].clamp(0.0, 1.0) * 255.0).round() as u8,
+            ]);
+            metallic_roughness_data.extend_from_slice(&[
+                255,
+                (roughness.clamp(0.0, 1.0) * 255.0).round() as u8,
+                (metallic * 255.0).round() as u8,
+                255,
+            ]);
+        }
     }
-}
\ No newline at end of file
+
+    return ((base_color_data, size), (metallic_roughness_data, size));
+}
+
+// KHR_texture_transform may also override the texCoord of the texture info
+fn get_gltf_texture_transform(
+    tex_coord: u32,
+    extension: Option<&gltf::json::Value>,
+) -> engine::scene::SceneTextureTransform {
+    let mut transform: engine::scene::SceneTextureTransform =
+        engine::scene::SceneTextureTransform {
+            tex_coord: tex_coord,
+            ..Default::default()
+        };
+    if extension.is_none() {
+        return transform;
+    }
+    let extension: &gltf::json::Value = extension.unwrap();
+
+    let get_pair = |key: &str| -> Option<[f32; 2]> {
+        let values = extension.get(key)?.as_array()?;
+        Some([
+            values.first()?.as_f64()? as f32,
+            values.get(1)?.as_f64()? as f32,
+        ])
+    };
+    if let Some(offset) = get_pair("offset") {
+        transform.offset = offset;
+    }
+    if let Some(scale) = get_pair("scale") {
+        transform.scale = scale;
+    }
+    if let Some(rotation) = extension.get("rotation").and_then(|value| value.as_f64()) {
+        transform.rotation = rotation as f32;
+    }
+    if let Some(tex_coord) = extension.get("texCoord").and_then(|value| value.as_u64()) {
+        transform.tex_coord = tex_coord as u32;
+    }
+
+    return transform;
+}
+
+fn get_gltf_sampler(sampler: &gltf::texture::Sampler<'_>) -> engine::scene::SceneSampler {
+    let get_wrap = |wrap: gltf::texture::WrappingMode| -> engine::scene::SamplerWrap {
+        match wrap {
+            gltf::texture::WrappingMode::ClampToEdge => engine::scene::SamplerWrap::ClampToEdge,
+            gltf::texture::WrappingMode::MirroredRepeat => {
+                engine::scene::SamplerWrap::MirroredRepeat
+            }
+            gltf::texture::WrappingMode::Repeat => engine::scene::SamplerWrap::Repeat,
+        }
+    };
+
+    // Undefined filters are left to the implementation, linear looks best
+    let mag_filter: engine::scene::SamplerFilter = match sampler.mag_filter() {
+        Some(gltf::texture::MagFilter::Nearest) => engine::scene::SamplerFilter::Nearest,
+        _ => engine::scene::SamplerFilter::Linear,
+    };
+    let (min_filter, mipmap_filter) = match sampler.min_filter() {
+        Some(gltf::texture::MinFilter::Nearest) => (engine::scene::SamplerFilter::Nearest, None),
+        Some(gltf::texture::MinFilter::Linear) => (engine::scene::SamplerFilter::Linear, None),
+        Some(gltf::texture::MinFilter::NearestMipmapNearest) => (
+            engine::scene::SamplerFilter::Nearest,
+            Some(engine::scene::SamplerFilter::Nearest),
+        ),
+        Some(gltf::texture::MinFilter::NearestMipmapLinear) => (
+            engine::scene::SamplerFilter::Nearest,
+            Some(engine::scene::SamplerFilter::Linear),
+        ),
+        Some(gltf::texture::MinFilter::LinearMipmapNearest) => (
+            engine::scene::SamplerFilter::Linear,
+            Some(engine::scene::SamplerFilter::Nearest),
+        ),
+        _ => (
+            engine::scene::SamplerFilter::Linear,
+            Some(engine::scene::SamplerFilter::Linear),
+        ),
+    };
+
+    engine::scene::SceneSampler {
+        wrap_u: get_wrap(sampler.wrap_s()),
+        wrap_v: get_wrap(sampler.wrap_t()),
+        mag_filter: mag_filter,
+        min_filter: min_filter,
+        mipmap_filter: mipmap_filter,
+    }
+}
@@ -0,0 +1,633 @@
+// EXT_meshopt_compression bitstream decoders, ported from meshoptimizer's reference codecs
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum CompressionMode {
+    Attributes,
+    Triangles,
+    Indices,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum CompressionFilter {
+    None,
+    Octahedral,
+    Quaternion,
+    Exponential,
+}
+
+const VERTEX_HEADER: u8 = 0xa0;
+const INDEX_HEADER: u8 = 0xe0;
+const SEQUENCE_HEADER: u8 = 0xd0;
+
+const BYTE_GROUP_SIZE: usize = 16;
+const VERTEX_BLOCK_SIZE_BYTES: usize = 8192;
+const VERTEX_BLOCK_MAX_SIZE: usize = 256;
+const VERTEX_TAIL_MIN_SIZE: usize = 32;
+const INDEX_TAIL_SIZE: usize = 16;
+const SEQUENCE_TAIL_SIZE: usize = 4;
+
+// Decode a compressed buffer view into count elements of stride bytes
+pub fn decode_buffer_view(
+    source: &[u8],
+    count: usize,
+    stride: usize,
+    mode: CompressionMode,
+    filter: CompressionFilter,
+) -> Result<Vec<u8>, String> {
+    if mode != CompressionMode::Attributes && filter != CompressionFilter::None {
+        return Err("filters only apply to ATTRIBUTES".to_string());
+    }
+
+    let mut data: Vec<u8> = match mode {
+        CompressionMode::Attributes => decode_vertex_buffer(source, count, stride)?,
+        CompressionMode::Triangles => decode_index_buffer(source, count, stride)?,
+        CompressionMode::Indices => decode_index_sequence(source, count, stride)?,
+    };
+
+    match filter {
+        CompressionFilter::None => {}
+        CompressionFilter::Octahedral => decode_octahedral_filter(&mut data, stride)?,
+        CompressionFilter::Quaternion => decode_quaternion_filter(&mut data, stride)?,
+        CompressionFilter::Exponential => decode_exponential_filter(&mut data, stride)?,
+    }
+    return Ok(data);
+}
+
+// Utility
+
+fn read_byte(data: &[u8], position: &mut usize) -> Result<u8, String> {
+    let byte: u8 = *data
+        .get(*position)
+        .ok_or("unexpected end of data".to_string())?;
+    *position += 1;
+    return Ok(byte);
+}
+
+// 7 bits per byte, least significant group first
+fn decode_vbyte(data: &[u8], position: &mut usize) -> Result<u32, String> {
+    let lead: u8 = read_byte(data, position)?;
+    if lead < 128 {
+        return Ok(lead as u32);
+    }
+
+    let mut result: u32 = (lead & 127) as u32;
+    let mut shift: u32 = 7;
+    for _ in 0..4 {
+        let group: u8 = read_byte(data, position)?;
+        result |= ((group & 127) as u32) << shift;
+        shift += 7;
+        if group < 128 {
+            break;
+        }
+    }
+    return Ok(result);
+}
+
+fn unzigzag8(value: u8) -> u8 {
+    return 0u8.wrapping_sub(value & 1) ^ (value >> 1);
+}
+
+fn unzigzag32(value: u32) -> u32 {
+    return 0u32.wrapping_sub(value & 1) ^ (value >> 1);
+}
+
+// Vertex codec
+
+// Every 16 deltas are stored as zeros, 2 bit, 4 bit or raw bytes, saturated values escape to a full byte
+fn decode_bytes_group(
+    data: &[u8],
+    position: &mut usize,
+    group: &mut [u8],
+    bits_log2: u8,
+) -> Result<(), String> {
+    match bits_log2 {
+        0 => group.fill(0),
+        1 | 2 => {
+            let bits: usize = 1 << bits_log2;
+            let packed_size: usize = BYTE_GROUP_SIZE * bits / 8;
+            let packed: &[u8] = data
+                .get(*position..*position + packed_size)
+                .ok_or("unexpected end of vertex data".to_string())?;
+            let escape_value: u8 = ((1 << bits) - 1) as u8;
+            let mut escape_position: usize = *position + packed_size;
+            for (i, value) in group.iter_mut().enumerate() {
+                // most significant bits first
+                let shift: usize = 8 - bits - (i * bits) % 8;
+                let packed_value: u8 = (packed[i * bits / 8] >> shift) & escape_value;
+                *value = if packed_value == escape_value {
+                    read_byte(data, &mut escape_position)?
+                } else {
+                    packed_value
+                };
+            }
+            *position = escape_position;
+        }
+        _ => {
+            let raw: &[u8] = data
+                .get(*position..*position + BYTE_GROUP_SIZE)
+                .ok_or("unexpected end of vertex data".to_string())?;
+            group.copy_from_slice(raw);
+            *position += BYTE_GROUP_SIZE;
+        }
+    }
+    return Ok(());
+}
+
+// One byte column of a vertex block, 2 header bits per group
+fn decode_bytes(data: &[u8], position: &mut usize, buffer: &mut [u8]) -> Result<(), String> {
+    let group_count: usize = buffer.len() / BYTE_GROUP_SIZE;
+    let header_size: usize = (group_count + 3) / 4;
+    let header: &[u8] = data
+        .get(*position..*position + header_size)
+        .ok_or("unexpected end of vertex data".to_string())?;
+    *position += header_size;
+
+    for (i, group) in buffer.chunks_exact_mut(BYTE_GROUP_SIZE).enumerate() {
+        let bits_log2: u8 = (header[i / 4] >> ((i % 4) * 2)) & 3;
+        decode_bytes_group(data, position, group, bits_log2)?;
+    }
+    return Ok(());
+}
+
+fn decode_vertex_buffer(data: &[u8], count: usize, stride: usize) -> Result<Vec<u8>, String> {
+    if stride == 0 || stride > 256 || stride % 4 != 0 {
+        return Err(format!("invalid vertex stride {}", stride));
+    }
+    let tail_size: usize = stride.max(VERTEX_TAIL_MIN_SIZE);
+    if data.len() < 1 + tail_size {
+        return Err("vertex data is too short".to_string());
+    }
+    if data[0] != VERTEX_HEADER {
+        return Err(format!("unsupported vertex header {:#x}", data[0]));
+    }
+
+    // deltas of the first vertex are relative to the tail, blocks never read into it
+    let mut last_vertex: Vec<u8> = data[data.len() - stride..].to_vec();
+    let blocks: &[u8] = &data[..data.len() - tail_size];
+    let block_size: usize =
+        ((VERTEX_BLOCK_SIZE_BYTES / stride) & !(BYTE_GROUP_SIZE - 1)).min(VERTEX_BLOCK_MAX_SIZE);
+
+    let mut out_data: Vec<u8> = vec![0; count * stride];
+    let mut buffer: [u8; VERTEX_BLOCK_MAX_SIZE] = [0; VERTEX_BLOCK_MAX_SIZE];
+    let mut position: usize = 1;
+    let mut vertex_offset: usize = 0;
+    while vertex_offset < count {
+        let block_count: usize = block_size.min(count - vertex_offset);
+        let aligned_count: usize = (block_count + BYTE_GROUP_SIZE - 1) & !(BYTE_GROUP_SIZE - 1);
+
+        for k in 0..stride {
+            decode_bytes(blocks, &mut position, &mut buffer[..aligned_count])?;
+
+            let mut previous: u8 = last_vertex[k];
+            for i in 0..block_count {
+                let value: u8 = unzigzag8(buffer[i]).wrapping_add(previous);
+                out_data[(vertex_offset + i) * stride + k] = value;
+                previous = value;
+            }
+            last_vertex[k] = previous;
+        }
+        vertex_offset += block_count;
+    }
+
+    if position != blocks.len() {
+        return Err("vertex data has trailing bytes".to_string());
+    }
+    return Ok(out_data);
+}
+
+// Index codec
+
+fn push_edge_fifo(fifo: &mut [[u32; 2]; 16], offset: &mut usize, a: u32, b: u32) {
+    fifo[*offset] = [a, b];
+    *offset = (*offset + 1) & 15;
+}
+
+fn push_vertex_fifo(fifo: &mut [u32; 16], offset: &mut usize, vertex: u32, condition: bool) {
+    fifo[*offset] = vertex;
+    *offset = (*offset + condition as usize) & 15;
+}
+
+fn decode_index(data: &[u8], position: &mut usize, last: u32) -> Result<u32, String> {
+    let delta: u32 = unzigzag32(decode_vbyte(data, position)?);
+    return Ok(last.wrapping_add(delta));
+}
+
+fn write_indices(indices: &[u32], stride: usize) -> Vec<u8> {
+    let mut out_data: Vec<u8> = Vec::with_capacity(indices.len() * stride);
+    for index in indices.iter() {
+        if stride == 2 {
+            out_data.extend_from_slice(&(*index as u16).to_le_bytes());
+        } else {
+            out_data.extend_from_slice(&index.to_le_bytes());
+        }
+    }
+    return out_data;
+}
+
+// Triangles reuse edges and vertices of recent triangles through two 16 entry fifos
+fn decode_index_buffer(data: &[u8], count: usize, stride: usize) -> Result<Vec<u8>, String> {
+    if stride != 2 && stride != 4 {
+        return Err(format!("invalid index stride {}", stride));
+    }
+    if count % 3 != 0 {
+        return Err(format!("index count {} is not a multiple of 3", count));
+    }
+    let triangle_count: usize = count / 3;
+    if data.len() < 1 + triangle_count + INDEX_TAIL_SIZE {
+        return Err("index data is too short".to_string());
+    }
+    let version: u8 = data[0] & 0x0f;
+    if data[0] & 0xf0 != INDEX_HEADER || version > 1 {
+        return Err(format!("unsupported index header {:#x}", data[0]));
+    }
+
+    let data_end: usize = data.len() - INDEX_TAIL_SIZE;
+    let codeaux_table: &[u8] = &data[data_end..];
+    let codes: &[u8] = &data[1..1 + triangle_count];
+    // version 1 spends the last two vertex fifo codes on last index -1 and +1
+    let fec_max: u8 = if version >= 1 { 13 } else { 15 };
+
+    let mut edge_fifo: [[u32; 2]; 16] = [[u32::MAX; 2]; 16];
+    let mut edge_offset: usize = 0;
+    let mut vertex_fifo: [u32; 16] = [u32::MAX; 16];
+    let mut vertex_offset: usize = 0;
+    let mut next: u32 = 0;
+    let mut last: u32 = 0;
+
+    let mut indices: Vec<u32> = Vec::with_capacity(count);
+    let mut position: usize = 1 + triangle_count;
+    for code in codes.iter() {
+        if position > data_end {
+            return Err("unexpected end of index data".to_string());
+        }
+
+        if *code < 0xf0 {
+            // edge from the fifo, third vertex is new, from the fifo or free
+            let fe: usize = (*code >> 4) as usize;
+            let [a, b]: [u32; 2] = edge_fifo[(edge_offset + 15 - fe) & 15];
+            let fec: u8 = *code & 15;
+
+            let c: u32 = if fec < fec_max {
+                let c: u32 = if fec == 0 {
+                    next
+                } else {
+                    vertex_fifo[(vertex_offset + 15 - fec as usize) & 15]
+                };
+                if fec == 0 {
+                    next = next.wrapping_add(1);
+                }
+                push_vertex_fifo(&mut vertex_fifo, &mut vertex_offset, c, fec == 0);
+                c
+            } else {
+                last = match fec {
+                    13 => last.wrapping_sub(1),
+                    14 => last.wrapping_add(1),
+                    _ => decode_index(data, &mut position, last)?,
+                };
+                push_vertex_fifo(&mut vertex_fifo, &mut vertex_offset, last, true);
+                last
+            };
+
+            indices.extend_from_slice(&[a, b, c]);
+            push_edge_fifo(&mut edge_fifo, &mut edge_offset, c, b);
+            push_edge_fifo(&mut edge_fifo, &mut edge_offset, a, c);
+        } else {
+            // no edge reuse, codeaux comes from the table or the next data byte
+            let is_table: bool = *code < 0xfe;
+            let codeaux: u8 = if is_table {
+                codeaux_table[(*code & 15) as usize]
+            } else {
+                read_byte(data, &mut position)?
+            };
+            let fea: u8 = if is_table || *code == 0xfe { 0 } else { 15 };
+            let feb: u8 = codeaux >> 4;
+            let fec: u8 = codeaux & 15;
+
+            // reset is encoded as a zero codeaux outside of the table
+            if !is_table && codeaux == 0 {
+                next = 0;
+            }
+
+            // next is advanced for all three vertices before free indices are decoded
+            let mut a: u32 = 0;
+            if fea == 0 {
+                a = next;
+                next = next.wrapping_add(1);
+            }
+            let mut b: u32 = vertex_fifo[(vertex_offset + 16 - feb as usize) & 15];
+            if feb == 0 {
+                b = next;
+                next = next.wrapping_add(1);
+            }
+            let mut c: u32 = vertex_fifo[(vertex_offset + 16 - fec as usize) & 15];
+            if fec == 0 {
+                c = next;
+                next = next.wrapping_add(1);
+            }
+
+            if !is_table {
+                if fea == 15 {
+                    last = decode_index(data, &mut position, last)?;
+                    a = last;
+                }
+                if feb == 15 {
+                    last = decode_index(data, &mut position, last)?;
+                    b = last;
+                }
+                if fec == 15 {
+                    last = decode_index(data, &mut position, last)?;
+                    c = last;
+                }
+            }
+
+            indices.extend_from_slice(&[a, b, c]);
+            let is_new_b: bool = feb == 0 || (!is_table && feb == 15);
+            let is_new_c: bool = fec == 0 || (!is_table && fec == 15);
+            push_vertex_fifo(&mut vertex_fifo, &mut vertex_offset, a, true);
+            push_vertex_fifo(&mut vertex_fifo, &mut vertex_offset, b, is_new_b);
+            push_vertex_fifo(&mut vertex_fifo, &mut vertex_offset, c, is_new_c);
+
+            push_edge_fifo(&mut edge_fifo, &mut edge_offset, b, a);
+            push_edge_fifo(&mut edge_fifo, &mut edge_offset, c, b);
+            push_edge_fifo(&mut edge_fifo, &mut edge_offset, a, c);
+        }
+    }
+
+    if position != data_end {
+        return Err("index data has trailing bytes".to_string());
+    }
+    return Ok(write_indices(&indices, stride));
+}
+
+// Each index is a delta from one of the two previous indices, the low bit picks which
+fn decode_index_sequence(data: &[u8], count: usize, stride: usize) -> Result<Vec<u8>, String> {
+    if stride != 2 && stride != 4 {
+        return Err(format!("invalid index stride {}", stride));
+    }
+    if data.len() < 1 + count + SEQUENCE_TAIL_SIZE {
+        return Err("index sequence data is too short".to_string());
+    }
+    if data[0] & 0xf0 != SEQUENCE_HEADER || data[0] & 0x0f > 1 {
+        return Err(format!("unsupported index sequence header {:#x}", data[0]));
+    }
+
+    let data_end: usize = data.len() - SEQUENCE_TAIL_SIZE;
+    let mut last: [u32; 2] = [0, 0];
+    let mut indices: Vec<u32> = Vec::with_capacity(count);
+    let mut position: usize = 1;
+    for _ in 0..count {
+        if position >= data_end {
+            return Err("unexpected end of index sequence data".to_string());
+        }
+        let value: u32 = decode_vbyte(data, &mut position)?;
+        let current: usize = (value & 1) as usize;
+        let index: u32 = last[current].wrapping_add(unzigzag32(value >> 1));
+        last[current] = index;
+        indices.push(index);
+    }
+
+    if position != data_end {
+        return Err("index sequence data has trailing bytes".to_string());
+    }
+    return Ok(write_indices(&indices, stride));
+}
+
+// Filters
+
+fn read_snorm(vertex: &[u8], component: usize, component_size: usize) -> i32 {
+    if component_size == 1 {
+        return vertex[component] as i8 as i32;
+    }
+    return i16::from_le_bytes([vertex[component * 2], vertex[component * 2 + 1]]) as i32;
+}
+
+fn write_snorm(vertex: &mut [u8], component: usize, component_size: usize, value: i32) {
+    if component_size == 1 {
+        vertex[component] = value as i8 as u8;
+    } else {
+        vertex[component * 2..component * 2 + 2].copy_from_slice(&(value as i16).to_le_bytes());
+    }
+}
+
+// Rounded float to int, away from zero like the reference decoder
+fn round_to_int(value: f32) -> i32 {
+    return (value + if value >= 0.0 { 0.5 } else { -0.5 }) as i32;
+}
+
+// Octahedral encoded unit vectors of 4 snorm8 or snorm16 components, w is kept
+fn decode_octahedral_filter(data: &mut [u8], stride: usize) -> Result<(), String> {
+    if stride != 4 && stride != 8 {
+        return Err(format!("invalid octahedral filter stride {}", stride));
+    }
+    let component_size: usize = stride / 4;
+    let max: f32 = if component_size == 1 { 127.0 } else { 32767.0 };
+
+    for vertex in data.chunks_exact_mut(stride) {
+        // z stores the encoded 1.0, the octahedron is unfolded for z < 0
+        let mut x: f32 = read_snorm(vertex, 0, component_size) as f32;
+        let mut y: f32 = read_snorm(vertex, 1, component_size) as f32;
+        let z: f32 = read_snorm(vertex, 2, component_size) as f32 - x.abs() - y.abs();
+        let t: f32 = z.min(0.0);
+        x += if x >= 0.0 { t } else { -t };
+        y += if y >= 0.0 { t } else { -t };
+
+        let scale: f32 = max / (x * x + y * y + z * z).sqrt();
+        write_snorm(vertex, 0, component_size, round_to_int(x * scale));
+        write_snorm(vertex, 1, component_size, round_to_int(y * scale));
+        write_snorm(vertex, 2, component_size, round_to_int(z * scale));
+    }
+    return Ok(());
+}
+
+// Three smallest quaternion components in snorm16, w stores the scale and the dropped component
+fn decode_quaternion_filter(data: &mut [u8], stride: usize) -> Result<(), String> {
+    if stride != 8 {
+        return Err(format!("invalid quaternion filter stride {}", stride));
+    }
+    let scale: f32 = 1.0 / 2.0_f32.sqrt();
+
+    for vertex in data.chunks_exact_mut(stride) {
+        let packed: i32 = read_snorm(vertex, 3, 2);
+        let component_scale: f32 = scale / (packed | 3) as f32;
+
+        let x: f32 = read_snorm(vertex, 0, 2) as f32 * component_scale;
+        let y: f32 = read_snorm(vertex, 1, 2) as f32 * component_scale;
+        let z: f32 = read_snorm(vertex, 2, 2) as f32 * component_scale;
+        // clamped, precision errors could go below zero
+        let w: f32 = (1.0 - x * x - y * y - z * z).max(0.0).sqrt();
+
+        // the dropped component goes back to its original slot
+        let dropped: usize = (packed & 3) as usize;
+        write_snorm(vertex, (dropped + 1) & 3, 2, round_to_int(x * 32767.0));
+        write_snorm(vertex, (dropped + 2) & 3, 2, round_to_int(y * 32767.0));
+        write_snorm(vertex, (dropped + 3) & 3, 2, round_to_int(z * 32767.0));
+        write_snorm(vertex, dropped, 2, round_to_int(w * 32767.0));
+    }
+    return Ok(());
+}
+
+// 24 bit signed mantissa and 8 bit signed exponent per 32 bit float
+fn decode_exponential_filter(data: &mut [u8], stride: usize) -> Result<(), String> {
+    if stride % 4 != 0 {
+        return Err(format!("invalid exponential filter stride {}", stride));
+    }
+
+    for word in data.chunks_exact_mut(4) {
+        let value: u32 = u32::from_le_bytes([word[0], word[1], word[2], word[3]]);
+        let mantissa: i32 = ((value << 8) as i32) >> 8;
+        let exponent: i32 = (value as i32) >> 24;
+        let decoded: f32 = f32::from_bits(((exponent + 127) as u32) << 23) * mantissa as f32;
+        word.copy_from_slice(&decoded.to_le_bytes());
+    }
+    return Ok(());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Streams and expected outputs are from meshoptimizer's reference encoder and test suite
+
+    const VERTEX_DATA: [u8; 62] = [
+        0xa0, 0x01, 0x2b, 0x00, 0x00, 0x00, 0x0f, 0x01, 0x3f, 0x00, 0x00, 0x00, 0x04, 0x04, 0xf8,
+        0x01, 0x3e, 0x00, 0x00, 0x00, 0x06, 0x06, 0x01, 0x3f, 0x00, 0x00, 0x00, 0x08, 0x08, 0x11,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00,
+    ];
+    const VERTEX_BUFFER: [u8; 16] = [0, 0, 0, 0, 1, 2, 3, 4, 2, 4, 6, 8, 250, 128, 7, 255];
+
+    const INDEX_DATA_V0: [u8; 27] = [
+        0xe0, 0xf0, 0x10, 0xfe, 0xff, 0xf0, 0x0c, 0xff, 0x02, 0x02, 0x02, 0x00, 0x76, 0x87, 0x56,
+        0x67, 0x78, 0xa9, 0x86, 0x65, 0x89, 0x68, 0x98, 0x01, 0x69, 0x00, 0x00,
+    ];
+    const INDEX_BUFFER_V0: [u32; 12] = [0, 1, 2, 2, 1, 3, 4, 6, 5, 7, 8, 9];
+
+    // restart and last index +-1 codes of version 1
+    const INDEX_DATA_V1: [u8; 24] = [
+        0xe1, 0xf0, 0x10, 0xfe, 0x1f, 0x3d, 0x00, 0x0a, 0x00, 0x76, 0x87, 0x56, 0x67, 0x78, 0xa9,
+        0x86, 0x65, 0x89, 0x68, 0x98, 0x01, 0x69, 0x00, 0x00,
+    ];
+    const INDEX_BUFFER_V1: [u32; 15] = [0, 1, 2, 2, 1, 3, 0, 1, 2, 2, 1, 5, 2, 1, 4];
+
+    const INDEX_SEQUENCE_DATA: [u8; 13] = [
+        0xd1, 0x00, 0x04, 0xcd, 0x01, 0x04, 0x07, 0x98, 0x1f, 0x00, 0x00, 0x00, 0x00,
+    ];
+    const INDEX_SEQUENCE: [u32; 6] = [0, 1, 51, 2, 49, 1000];
+
+    fn to_bytes_u16(values: &[u32]) -> Vec<u8> {
+        return values
+            .iter()
+            .flat_map(|value| (*value as u16).to_le_bytes())
+            .collect::<Vec<_>>();
+    }
+
+    fn to_bytes_u32(values: &[u32]) -> Vec<u8> {
+        return values
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect::<Vec<_>>();
+    }
+
+    fn to_bytes_i16(values: &[i16]) -> Vec<u8> {
+        return values
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect::<Vec<_>>();
+    }
+
+    #[test]
+    fn decode_vertex() {
+        let decoded: Vec<u8> = decode_buffer_view(
+            &VERTEX_DATA,
+            4,
+            4,
+            CompressionMode::Attributes,
+            CompressionFilter::None,
+        )
+        .unwrap();
+        assert_eq!(decoded, VERTEX_BUFFER);
+    }
+
+    #[test]
+    fn decode_index_v0() {
+        let decoded: Vec<u8> =
+            decode_index_buffer(&INDEX_DATA_V0, INDEX_BUFFER_V0.len(), 4).unwrap();
+        assert_eq!(decoded, to_bytes_u32(&INDEX_BUFFER_V0));
+
+        let decoded: Vec<u8> =
+            decode_index_buffer(&INDEX_DATA_V0, INDEX_BUFFER_V0.len(), 2).unwrap();
+        assert_eq!(decoded, to_bytes_u16(&INDEX_BUFFER_V0));
+    }
+
+    #[test]
+    fn decode_index_v1() {
+        let decoded: Vec<u8> =
+            decode_index_buffer(&INDEX_DATA_V1, INDEX_BUFFER_V1.len(), 4).unwrap();
+        assert_eq!(decoded, to_bytes_u32(&INDEX_BUFFER_V1));
+    }
+
+    #[test]
+    fn decode_sequence() {
+        let decoded: Vec<u8> =
+            decode_index_sequence(&INDEX_SEQUENCE_DATA, INDEX_SEQUENCE.len(), 4).unwrap();
+        assert_eq!(decoded, to_bytes_u32(&INDEX_SEQUENCE));
+    }
+
+    #[test]
+    fn decode_truncated() {
+        for size in 0..VERTEX_DATA.len() {
+            assert!(decode_vertex_buffer(&VERTEX_DATA[..size], 4, 4).is_err());
+        }
+        for size in 0..INDEX_DATA_V0.len() {
+            assert!(decode_index_buffer(&INDEX_DATA_V0[..size], INDEX_BUFFER_V0.len(), 4).is_err());
+        }
+        for size in 0..INDEX_DATA_V1.len() {
+            assert!(decode_index_buffer(&INDEX_DATA_V1[..size], INDEX_BUFFER_V1.len(), 4).is_err());
+        }
+        for size in 0..INDEX_SEQUENCE_DATA.len() {
+            assert!(
+                decode_index_sequence(&INDEX_SEQUENCE_DATA[..size], INDEX_SEQUENCE.len(), 4)
+                    .is_err()
+            );
+        }
+    }
+
+    #[test]
+    fn decode_octahedral() {
+        let mut data: [u8; 16] = [
+            0, 1, 127, 0, 0, 187, 127, 1, 255, 1, 127, 0, 14, 130, 127, 1,
+        ];
+        decode_octahedral_filter(&mut data, 4).unwrap();
+        assert_eq!(
+            data,
+            [0, 1, 127, 0, 0, 159, 82, 1, 255, 1, 127, 0, 1, 130, 241, 1]
+        );
+
+        let mut data: Vec<u8> = to_bytes_i16(&[0, 1, 32767, 0, 1000, -2000, 32767, -1]);
+        decode_octahedral_filter(&mut data, 8).unwrap();
+        assert_eq!(
+            data,
+            to_bytes_i16(&[0, 1, 32767, 0, 1098, -2195, 32675, -1])
+        );
+    }
+
+    #[test]
+    fn decode_quaternion() {
+        let mut data: Vec<u8> = to_bytes_i16(&[
+            0, 0, 0, 0x7fff, 23170, 23170, 23170, 0x7ffc, 4000, -3000, 0, 0x7ffd,
+        ]);
+        decode_quaternion_filter(&mut data, 8).unwrap();
+        assert_eq!(
+            data,
+            to_bytes_i16(&[0, 0, 0, 32767, 16383, 16384, 16384, 16384, 0, 32576, 2828, -2121])
+        );
+    }
+
+    #[test]
+    fn decode_exponential() {
+        let mut data: Vec<u8> = to_bytes_u32(&[0xff000003, 0x00000007, 0x02fffffd]);
+        decode_exponential_filter(&mut data, 4).unwrap();
+        assert_eq!(data, to_bytes_u32(&[0x3fc00000, 0x40e00000, 0xc1400000]));
+    }
+}
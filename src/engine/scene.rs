@@ -1,4 +1,4 @@
-use crate::{frontend, rendering};
+use crate::{engine, frontend, rendering};
 
 use glam::Vec4Swizzles;
 
@@ -8,9 +8,21 @@ pub struct Scene {
     pub objects: Vec<SceneObject>,
     pub batched_objects: Vec<SceneObject>,
     pub materials: Vec<SceneMaterial>,
+    pub skins: Vec<SceneSkin>,
+    pub animations: Vec<engine::animation::AnimationClip>,
+    pub animation_player: engine::animation::AnimationPlayer,
+    pub cameras: Vec<SceneCamera>,
+    pub lights: Vec<SceneLight>,
+    // loaded .gltf, scenes are switched by reloading it
+    pub gltf_path: std::string::String,
+    pub load_options: engine::load::LoadOptions,
+    pub scenes: Vec<std::string::String>,
+    pub active_scene: Option<usize>,
     // world variables
     pub eye_location: glam::Vec3,
     pub eye_direction: glam::Vec3,
+    pub camera_projection: CameraProjection,
+    pub active_camera: Option<usize>,
     pub directional_light_angle: [f32; 3],
     pub ambient_light_color: [f32; 4],
     pub background_color: [f32; 4],
@@ -29,6 +41,8 @@ impl Scene {
             z: 0.5,
         };
         self.eye_direction = -glam::Vec3::X;
+        self.camera_projection = CameraProjection::default();
+        self.active_camera = None;
         self.directional_light_angle = [0.0, 0.0, -1.0];
         self.ambient_light_color = [0.0, 0.0, 0.0, 1.0];
         self.background_color = [0.7, 0.7, 0.7, 1.0];
@@ -39,6 +53,122 @@ impl Scene {
         self.is_first_update = true;
         self.use_batched = true;
     }
+
+    // Dynamic objects move every frame, blended ones are sorted per submesh, neither is batched
+    pub fn is_batched(&self, object: &SceneObject) -> bool {
+        if object.is_dynamic {
+            return false;
+        }
+        let has_blend: bool = object.source_mesh.as_ref().map_or(false, |mesh| {
+            mesh.borrow().submeshes.iter().any(|submesh| {
+                submesh
+                    .material
+                    .and_then(|material| self.materials.get(material as usize))
+                    .map_or(false, |material| material.alpha_mode == AlphaMode::Blend)
+            })
+        });
+        return has_blend == false;
+    }
+
+    // Objects drawn this frame
+    pub fn get_render_objects(&self) -> Vec<&SceneObject> {
+        if self.use_batched == false {
+            return self.objects.iter().collect::<Vec<_>>();
+        }
+        return self
+            .batched_objects
+            .iter()
+            .chain(
+                self.objects
+                    .iter()
+                    .filter(|object| !self.is_batched(object)),
+            )
+            .collect::<Vec<_>>();
+    }
+
+    // Skinning matrices of a skinned object, relative to the object itself
+    pub fn get_joint_matrices(&self, object: &SceneObject) -> Vec<[f32; 16]> {
+        let skin: &SceneSkin = &self.skins[object.skin.unwrap() as usize];
+        let inverse_object_matrix: glam::Mat4 =
+            glam::Mat4::from_cols_array_2d(&object.world_transform).inverse();
+
+        let mut joint_matrices: Vec<[f32; 16]> = Vec::with_capacity(skin.joints.len());
+        for (i, joint) in skin.joints.iter().enumerate() {
+            let joint_matrix: glam::Mat4 =
+                glam::Mat4::from_cols_array_2d(&self.objects[*joint as usize].world_transform);
+            let inverse_bind_matrix: glam::Mat4 =
+                glam::Mat4::from_cols_array_2d(&skin.inverse_bind_matrices[i]);
+            joint_matrices
+                .push((inverse_object_matrix * joint_matrix * inverse_bind_matrix).to_cols_array());
+        }
+
+        return joint_matrices;
+    }
+
+    // Move the eye to a glTF camera, None goes back to the free camera projection
+    pub fn set_active_camera(&mut self, camera_index: Option<usize>) {
+        self.active_camera = camera_index;
+        let camera: &SceneCamera = match camera_index {
+            Some(index) => &self.cameras[index],
+            None => {
+                self.camera_projection = CameraProjection::default();
+                return;
+            }
+        };
+
+        let mut camera_matrix: glam::Mat4 =
+            glam::Mat4::from_cols_array_2d(&self.objects[camera.node as usize].world_transform);
+        if self.convert_y_to_z {
+            let y_to_z_mat: glam::Mat4 = glam::Mat4::from_axis_angle(
+                glam::Vec3::new(1.0, 0.0, 0.0),
+                std::f32::consts::PI / 2.0,
+            );
+            camera_matrix = y_to_z_mat * camera_matrix;
+        }
+
+        // glTF cameras look down their local -Z, roll is dropped by the Z-up view
+        self.eye_location = camera_matrix.transform_point3(glam::Vec3::ZERO);
+        self.eye_direction = camera_matrix.transform_vector3(-glam::Vec3::Z).normalize();
+        self.camera_projection = camera.projection;
+    }
+
+    pub fn get_projection_matrix(&self, aspect_ratio: f32) -> glam::Mat4 {
+        match self.camera_projection {
+            CameraProjection::Perspective {
+                yfov,
+                znear,
+                zfar: Some(zfar),
+            } => glam::Mat4::perspective_rh(yfov, aspect_ratio, znear, zfar),
+            CameraProjection::Perspective {
+                yfov,
+                znear,
+                zfar: None,
+            } => glam::Mat4::perspective_infinite_rh(yfov, aspect_ratio, znear),
+            CameraProjection::Orthographic { ymag, znear, zfar } => {
+                // keep the viewport aspect instead of the authored xmag
+                let xmag: f32 = ymag * aspect_ratio;
+                glam::Mat4::orthographic_rh(-xmag, xmag, -ymag, ymag, znear, zfar)
+            }
+        }
+    }
+
+    // Rebuild world transforms from node local matrices, indexed by object index
+    pub fn update_world_transforms(&mut self, local_matrices: &Vec<glam::Mat4>) {
+        let mut matrix_vec: Vec<[[f32; 4]; 4]> = Vec::with_capacity(self.objects.len());
+        for object in self.objects.iter() {
+            let mut model_matrix: glam::Mat4 = local_matrices[object.index as usize];
+            let mut parent_option: Option<u32> = object.parent_index;
+            while parent_option.is_some() {
+                let parent_index: usize = parent_option.unwrap() as usize;
+                model_matrix = local_matrices[parent_index] * model_matrix;
+                parent_option = self.objects[parent_index].parent_index;
+            }
+            matrix_vec.push(model_matrix.to_cols_array_2d());
+        }
+        for i in 0..matrix_vec.len() {
+            self.objects[i].world_transform = matrix_vec[i];
+        }
+    }
 }
 
 #[derive(Clone, Default)]
@@ -48,10 +178,81 @@ pub struct SceneObject {
     pub parent_index: Option<u32>,
     pub child_index: Vec<u32>,
     pub world_transform: [[f32; 4]; 4],
+    // rest pose local transform
+    pub translation: [f32; 3],
+    pub rotation: [f32; 4],
+    pub scale: [f32; 3],
     pub source_mesh: Option<std::rc::Rc<std::cell::RefCell<rendering::common::Mesh>>>,
     pub shading_type: u8,
     pub render_resource:
         Option<std::rc::Rc<std::cell::RefCell<rendering::webgpu::WebGPURenderResource>>>,
+    pub skin: Option<u32>,
+    // blend shape weights, one per mesh morph target
+    pub morph_weights: Vec<f32>,
+    // transformed every frame, excluded from batching
+    pub is_dynamic: bool,
+}
+
+#[derive(Clone, Default)]
+pub struct SceneCamera {
+    pub _name: Option<std::string::String>,
+    // object of the node holding the camera, its world transform places the eye
+    pub node: u32,
+    pub projection: CameraProjection,
+}
+
+#[derive(Clone, Copy)]
+pub enum CameraProjection {
+    Perspective {
+        yfov: f32,
+        znear: f32,
+        zfar: Option<f32>,
+    },
+    Orthographic {
+        ymag: f32,
+        znear: f32,
+        zfar: f32,
+    },
+}
+impl Default for CameraProjection {
+    fn default() -> Self {
+        CameraProjection::Perspective {
+            yfov: std::f32::consts::FRAC_PI_4,
+            znear: 0.01,
+            zfar: Some(100.0),
+        }
+    }
+}
+
+// KHR_lights_punctual, placed by the world transform of its node
+#[derive(Clone, Default)]
+pub struct SceneLight {
+    pub _name: Option<std::string::String>,
+    // object of the node holding the light
+    pub node: u32,
+    pub kind: LightKind,
+    pub color: [f32; 3],
+    // candela for point and spot, lux for directional
+    pub intensity: f32,
+    // None is infinite
+    pub range: Option<f32>,
+    pub inner_cone_angle: f32,
+    pub outer_cone_angle: f32,
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum LightKind {
+    #[default]
+    Directional,
+    Point,
+    Spot,
+}
+
+#[derive(Clone, Default)]
+pub struct SceneSkin {
+    pub _name: Option<std::string::String>,
+    pub joints: Vec<u32>,
+    pub inverse_bind_matrices: Vec<[[f32; 4]; 4]>,
 }
 
 #[derive(Clone, Default)]
@@ -63,6 +264,93 @@ pub struct SceneMaterial {
     pub normal_texture_size: [u32; 2],
     pub metallic_roughness_texture: Vec<u8>,
     pub metallic_roughness_texture_size: [u32; 2],
+    pub emissive_texture: Vec<u8>,
+    pub emissive_texture_size: [u32; 2],
+    pub occlusion_texture: Vec<u8>,
+    pub occlusion_texture_size: [u32; 2],
+    pub base_color_sampler: SceneSampler,
+    pub normal_sampler: SceneSampler,
+    pub metallic_roughness_sampler: SceneSampler,
+    pub emissive_sampler: SceneSampler,
+    pub occlusion_sampler: SceneSampler,
+    pub base_color_transform: SceneTextureTransform,
+    pub normal_transform: SceneTextureTransform,
+    pub metallic_roughness_transform: SceneTextureTransform,
+    pub emissive_transform: SceneTextureTransform,
+    pub occlusion_transform: SceneTextureTransform,
+    pub base_color_factor: [f32; 4],
+    pub metallic_factor: f32,
+    pub roughness_factor: f32,
+    pub emissive_factor: [f32; 3],
+    pub occlusion_strength: f32,
+    pub alpha_mode: AlphaMode,
+    pub alpha_cutoff: f32,
+    pub double_sided: bool,
+    // KHR_materials_unlit, base color is output without lighting
+    pub unlit: bool,
+    // KHR_materials_specular, strength and color of the dielectric highlight,
+    // shaded as a tinted Blinn-Phong highlight rather than an F0 scale
+    pub specular_factor: f32,
+    pub specular_color_factor: [f32; 3],
+    // KHR_materials_clearcoat, factors only, no clearcoat normal map
+    pub clearcoat_factor: f32,
+    pub clearcoat_roughness_factor: f32,
+    // KHR_materials_sheen, factors only
+    pub sheen_color_factor: [f32; 3],
+    pub sheen_roughness_factor: f32,
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum AlphaMode {
+    #[default]
+    Opaque,
+    Mask,
+    Blend,
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct SceneSampler {
+    pub wrap_u: SamplerWrap,
+    pub wrap_v: SamplerWrap,
+    pub mag_filter: SamplerFilter,
+    pub min_filter: SamplerFilter,
+    // None samples only the base level, as glTF NEAREST and LINEAR minification do
+    pub mipmap_filter: Option<SamplerFilter>,
+}
+
+// TEXCOORD set and KHR_texture_transform of one texture slot
+#[derive(Clone, Copy, PartialEq)]
+pub struct SceneTextureTransform {
+    pub tex_coord: u32,
+    pub offset: [f32; 2],
+    // counter-clockwise (radian)
+    pub rotation: f32,
+    pub scale: [f32; 2],
+}
+impl Default for SceneTextureTransform {
+    fn default() -> Self {
+        SceneTextureTransform {
+            tex_coord: 0,
+            offset: [0.0, 0.0],
+            rotation: 0.0,
+            scale: [1.0, 1.0],
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum SamplerWrap {
+    #[default]
+    Repeat,
+    ClampToEdge,
+    MirroredRepeat,
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum SamplerFilter {
+    #[default]
+    Linear,
+    Nearest,
 }
 
 #[derive(Clone, Copy, Default)]
@@ -76,61 +364,83 @@ pub enum ShadingType {
 // Util
 
 pub fn batch_objects(scene: &std::rc::Rc<std::cell::RefCell<Scene>>) {
-    let mut batch_map: std::collections::HashMap<u32, rendering::common::Mesh> =
-        std::collections::HashMap::with_capacity(scene.borrow().objects.len());
-    for object in scene.borrow().objects.iter() {
-        if object.source_mesh.is_some() {
-            let source_mesh = object.source_mesh.as_ref().unwrap();
-            let material_option = source_mesh.borrow().material;
-            if material_option.is_some() {
-                let material = material_option.as_ref().unwrap();
-                // init
-                if batch_map.get(material).is_none() {
-                    batch_map.insert(*material, rendering::common::Mesh::default());
+    // lines and points never merge with triangles of the same material
+    let mut batch_map: std::collections::HashMap<
+        (u32, rendering::common::Topology),
+        rendering::common::Mesh,
+    > = std::collections::HashMap::with_capacity(scene.borrow().objects.len());
+    let scene_value = scene.borrow();
+    for object in scene_value.objects.iter() {
+        if object.source_mesh.is_some() && scene_value.is_batched(object) {
+            let source_mesh = object.source_mesh.as_ref().unwrap().borrow();
+            let trans_matrix = glam::Mat4::from_cols_array_2d(&object.world_transform);
+            let rotation_matrix =
+                glam::Mat4::from_quat(trans_matrix.to_scale_rotation_translation().1);
+
+            for submesh in source_mesh.submeshes.iter() {
+                if submesh.material.is_none() {
+                    // no material not create batched mesh
+                    continue;
                 }
-                // batch
-                let batched_mesh = batch_map.get_mut(material).unwrap();
-                let mut source_vertices = source_mesh.borrow().vertices.clone();
-                let indices_offset = batched_mesh.vertices.len() as u32;
-                let trans_matrix = glam::Mat4::from_cols_array_2d(&object.world_transform);
-                let rotation_matrix =
-                    glam::Mat4::from_quat(trans_matrix.to_scale_rotation_translation().1);
-                for i in 0..source_vertices.len() {
-                    let vert = glam::Vec4::from_array(source_vertices[i].pos);
+                let material = submesh.material.unwrap();
+                // init
+                let batched_mesh = batch_map
+                    .entry((material, submesh.topology))
+                    .or_insert_with(|| rendering::common::Mesh {
+                        submeshes: vec![rendering::common::SubMesh {
+                            index_offset: 0,
+                            index_count: 0,
+                            material: Some(material),
+                            topology: submesh.topology,
+                        }],
+                        ..Default::default()
+                    });
+
+                // batch only vertices referenced by this submesh
+                let begin: usize = submesh.index_offset as usize;
+                let end: usize = begin + submesh.index_count as usize;
+                let mut index_map: std::collections::HashMap<u32, u32> =
+                    std::collections::HashMap::new();
+                for source_index in source_mesh.indices[begin..end].iter() {
+                    if let Some(batched_index) = index_map.get(source_index) {
+                        batched_mesh.indices.push(*batched_index);
+                        continue;
+                    }
+
+                    let mut vertex = source_mesh.vertices[*source_index as usize];
+                    let vert = glam::Vec4::from_array(vertex.pos);
                     let transed_vert = trans_matrix.mul_vec4(vert);
-                    source_vertices[i].pos = transed_vert.to_array();
-                    let norm = glam::Vec4::new(
-                        source_vertices[i].normal[0],
-                        source_vertices[i].normal[1],
-                        source_vertices[i].normal[2],
-                        1.0,
-                    );
+                    vertex.pos = transed_vert.to_array();
+                    let norm =
+                        glam::Vec4::new(vertex.normal[0], vertex.normal[1], vertex.normal[2], 1.0);
                     let transed_norm = rotation_matrix.mul_vec4(norm);
-                    source_vertices[i].normal = [transed_norm.x, transed_norm.y, transed_norm.z];
+                    vertex.normal = [transed_norm.x, transed_norm.y, transed_norm.z];
                     let tangent = glam::Vec4::new(
-                        source_vertices[i].tangent[0],
-                        source_vertices[i].tangent[1],
-                        source_vertices[i].tangent[2],
+                        vertex.tangent[0],
+                        vertex.tangent[1],
+                        vertex.tangent[2],
                         1.0,
                     );
                     let transed_tangent = rotation_matrix.mul_vec4(tangent);
-                    source_vertices[i].tangent =
-                        [transed_tangent.x, transed_tangent.y, transed_tangent.z];
-                }
-                batched_mesh.vertices.append(&mut source_vertices);
+                    vertex.tangent = [
+                        transed_tangent.x,
+                        transed_tangent.y,
+                        transed_tangent.z,
+                        vertex.tangent[3],
+                    ];
 
-                let mut source_indices = source_mesh.borrow().indices.clone();
-                for index in source_indices.iter_mut() {
-                    *index += indices_offset;
+                    let batched_index = batched_mesh.vertices.len() as u32;
+                    batched_mesh.vertices.push(vertex);
+                    batched_mesh.indices.push(batched_index);
+                    index_map.insert(*source_index, batched_index);
                 }
-                batched_mesh.indices.append(&mut source_indices);
-                batched_mesh.material = Some(*material);
-            } else {
-                // no material not create batched mesh
+                batched_mesh.submeshes[0].index_count = batched_mesh.indices.len() as u32;
             }
         }
     }
 
+    drop(scene_value);
+
     for batch_pair in batch_map {
         let batched_object = SceneObject {
             _name: Some("batched".to_string()),
@@ -138,7 +448,7 @@ pub fn batch_objects(scene: &std::rc::Rc<std::cell::RefCell<Scene>>) {
             world_transform: glam::Mat4::IDENTITY.to_cols_array_2d(),
             source_mesh: Some(std::rc::Rc::new(std::cell::RefCell::new(batch_pair.1))),
             render_resource: None,
-            index: batch_pair.0,
+            index: batch_pair.0 .0,
             ..Default::default()
         };
         scene.borrow_mut().batched_objects.push(batched_object);
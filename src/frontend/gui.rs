@@ -6,6 +6,16 @@ pub fn start_gui(scene: &std::rc::Rc<std::cell::RefCell<engine::scene::Scene>>)
     create_view_dialog(scene);
 }
 
+pub fn show_error_message(message: &str) {
+    let body: web_sys::HtmlElement = gloo::utils::body();
+
+    let error_element: web_sys::Element = gloo::utils::document().create_element("div").unwrap();
+    error_element.set_id("error-message");
+    error_element.set_text_content(Some(message));
+
+    body.append_child(&error_element).unwrap();
+}
+
 fn create_panels() {
     let body: web_sys::HtmlElement = gloo::utils::body();
 
@@ -585,6 +595,100 @@ fn create_view_dialog(scene: &std::rc::Rc<std::cell::RefCell<engine::scene::Scen
         let accordion_content_element = gloo::utils::document().create_element("div").unwrap();
         accordion_content_element.set_class_name("accordion-content");
 
+        // scene
+        {
+            let scene_element: web_sys::Element =
+                gloo::utils::document().create_element("div").unwrap();
+            scene_element.set_class_name("widget-row");
+
+            let scene_label_element: web_sys::Element =
+                gloo::utils::document().create_element("div").unwrap();
+            scene_label_element.set_class_name("widget-label");
+            scene_label_element.set_text_content(Some("Scene"));
+
+            let scene_select_element = gloo::utils::document().create_element("select").unwrap();
+            scene_select_element.set_class_name("widget-value select-element");
+            scene_select_element.set_id("scene-select");
+
+            if scene_value.scenes.len() <= 1 {
+                scene_select_element.set_attribute("disabled", "").unwrap();
+            }
+            if scene_value.scenes.is_empty() {
+                let scene_option_none = gloo::utils::document().create_element("option").unwrap();
+                scene_option_none.set_text_content(Some("none"));
+                scene_select_element.append_child(&scene_option_none).unwrap();
+            }
+            for (i, scene_name) in scene_value.scenes.iter().enumerate() {
+                let scene_option = gloo::utils::document().create_element("option").unwrap();
+                scene_option.set_attribute("value", &i.to_string()).unwrap();
+                scene_option.set_text_content(Some(scene_name.as_str()));
+                if scene_value.active_scene == Some(i) {
+                    scene_option.set_attribute("selected", "").unwrap();
+                }
+                scene_select_element.append_child(&scene_option).unwrap();
+            }
+
+            {
+                let scene_clone: std::rc::Rc<std::cell::RefCell<engine::scene::Scene>> =
+                    scene.clone();
+
+                let scene_closure: wasm_bindgen::prelude::Closure<dyn FnMut(_)> =
+                    wasm_bindgen::closure::Closure::wrap(Box::new(
+                        move |_event: web_sys::InputEvent| {
+                            let scene_element: web_sys::Element = gloo::utils::document()
+                                .get_element_by_id("scene-select")
+                                .unwrap();
+                            let scene_element: web_sys::HtmlSelectElement =
+                                scene_element.dyn_into().unwrap();
+                            let value: String = scene_element.value();
+
+                            let scene_clone: std::rc::Rc<
+                                std::cell::RefCell<engine::scene::Scene>,
+                            > = scene_clone.clone();
+                            let gltf_path: String = scene_clone.borrow().gltf_path.clone();
+                            let mut load_options: engine::load::LoadOptions =
+                                scene_clone.borrow().load_options;
+                            load_options.scene_index = value.parse::<usize>().ok();
+
+                            // reload only the nodes of the chosen scene
+                            wasm_bindgen_futures::spawn_local(async move {
+                                if let Err(error) = engine::load::load_gltf_into_scene(
+                                    &scene_clone,
+                                    &gltf_path,
+                                    &load_options,
+                                )
+                                .await
+                                {
+                                    log::error!("{}", error);
+                                    show_error_message(&error.to_string());
+                                }
+                                refresh_view_dialog(&scene_clone);
+                            });
+                        },
+                    )
+                        as Box<dyn FnMut(_)>);
+
+                scene_select_element
+                    .add_event_listener_with_callback(
+                        "change",
+                        scene_closure.as_ref().unchecked_ref(),
+                    )
+                    .unwrap();
+                scene_closure.forget();
+            }
+
+            scene_element
+                .append_child(&scene_label_element)
+                .unwrap();
+            scene_element
+                .append_child(&scene_select_element)
+                .unwrap();
+
+            accordion_content_element
+                .append_child(&scene_element)
+                .unwrap();
+        }
+
         // render type
         {
             let render_type_element: web_sys::Element =
@@ -666,6 +770,82 @@ fn create_view_dialog(scene: &std::rc::Rc<std::cell::RefCell<engine::scene::Scen
                 .unwrap();
         }
 
+        // camera
+        {
+            let camera_element: web_sys::Element =
+                gloo::utils::document().create_element("div").unwrap();
+            camera_element.set_class_name("widget-row");
+
+            let camera_label_element: web_sys::Element =
+                gloo::utils::document().create_element("div").unwrap();
+            camera_label_element.set_class_name("widget-label");
+            camera_label_element.set_text_content(Some("Camera"));
+
+            let camera_select_element = gloo::utils::document().create_element("select").unwrap();
+            camera_select_element.set_class_name("widget-value select-element");
+            camera_select_element.set_id("camera-select");
+
+            let camera_option_free = gloo::utils::document().create_element("option").unwrap();
+            camera_option_free.set_attribute("value", "free").unwrap();
+            camera_option_free.set_text_content(Some("free"));
+            camera_select_element.append_child(&camera_option_free).unwrap();
+            for (i, camera) in scene_value.cameras.iter().enumerate() {
+                let camera_option = gloo::utils::document().create_element("option").unwrap();
+                camera_option.set_attribute("value", &i.to_string()).unwrap();
+                camera_option.set_text_content(Some(
+                    camera
+                        ._name
+                        .clone()
+                        .unwrap_or(format!("camera {}", i))
+                        .as_str(),
+                ));
+                if scene_value.active_camera == Some(i) {
+                    camera_option.set_attribute("selected", "").unwrap();
+                }
+                camera_select_element.append_child(&camera_option).unwrap();
+            }
+
+            {
+                let scene_clone: std::rc::Rc<std::cell::RefCell<engine::scene::Scene>> =
+                    scene.clone();
+
+                let camera_closure: wasm_bindgen::prelude::Closure<dyn FnMut(_)> =
+                    wasm_bindgen::closure::Closure::wrap(Box::new(
+                        move |_event: web_sys::InputEvent| {
+                            let camera_element: web_sys::Element = gloo::utils::document()
+                                .get_element_by_id("camera-select")
+                                .unwrap();
+                            let camera_element: web_sys::HtmlSelectElement =
+                                camera_element.dyn_into().unwrap();
+                            let value: String = camera_element.value();
+
+                            let mut scene_value = scene_clone.borrow_mut();
+                            scene_value.set_active_camera(value.parse::<usize>().ok());
+                        },
+                    )
+                        as Box<dyn FnMut(_)>);
+
+                camera_select_element
+                    .add_event_listener_with_callback(
+                        "change",
+                        camera_closure.as_ref().unchecked_ref(),
+                    )
+                    .unwrap();
+                camera_closure.forget();
+            }
+
+            camera_element
+                .append_child(&camera_label_element)
+                .unwrap();
+            camera_element
+                .append_child(&camera_select_element)
+                .unwrap();
+
+            accordion_content_element
+                .append_child(&camera_element)
+                .unwrap();
+        }
+
         // clear color
         {
             let clearcolor_element: web_sys::Element =
@@ -780,6 +960,9 @@ fn create_view_dialog(scene: &std::rc::Rc<std::cell::RefCell<engine::scene::Scen
             let buffer_type_option_metallic =
             gloo::utils::document().create_element("option").unwrap();
             buffer_type_option_metallic.set_text_content(Some("metallic"));
+            let buffer_type_option_emissive =
+                gloo::utils::document().create_element("option").unwrap();
+            buffer_type_option_emissive.set_text_content(Some("emissive"));
 
             {
                 let scene_clone: std::rc::Rc<std::cell::RefCell<engine::scene::Scene>> =
@@ -802,6 +985,7 @@ fn create_view_dialog(scene: &std::rc::Rc<std::cell::RefCell<engine::scene::Scen
                                 "depth" => scene_value.differed_debug_type = 2,
                                 "albedo" => scene_value.differed_debug_type = 3,
                                 "metallic" => scene_value.differed_debug_type = 4,
+                                "emissive" => scene_value.differed_debug_type = 5,
                                 _ => scene_value.differed_debug_type = 0,
                             }
                         },
@@ -832,6 +1016,9 @@ fn create_view_dialog(scene: &std::rc::Rc<std::cell::RefCell<engine::scene::Scen
             buffer_type_select_element
                 .append_child(&buffer_type_option_metallic)
                 .unwrap();
+            buffer_type_select_element
+                .append_child(&buffer_type_option_emissive)
+                .unwrap();
 
             buffer_type_element
                 .append_child(&buffer_type_label_element)
@@ -845,6 +1032,434 @@ fn create_view_dialog(scene: &std::rc::Rc<std::cell::RefCell<engine::scene::Scen
                 .unwrap();
         }
 
+        // animation
+        {
+            let animation_accordion_input_element =
+                gloo::utils::document().create_element("input").unwrap();
+            let animation_accordion_input_element: web_sys::HtmlInputElement =
+                animation_accordion_input_element.dyn_into().unwrap();
+            animation_accordion_input_element
+                .set_attribute("type", "checkbox")
+                .unwrap();
+            animation_accordion_input_element.set_class_name("accordion-input");
+            animation_accordion_input_element.set_id("accordion-animation");
+            animation_accordion_input_element.set_checked(true);
+
+            let animation_accordion_label_element =
+                gloo::utils::document().create_element("label").unwrap();
+            animation_accordion_label_element
+                .set_class_name("accordion-label inner-accordion-label");
+            animation_accordion_label_element.set_text_content(Some("Animation"));
+            animation_accordion_label_element
+                .set_attribute("for", "accordion-animation")
+                .unwrap();
+
+            let animation_accordion_content_element =
+                gloo::utils::document().create_element("div").unwrap();
+            animation_accordion_content_element
+                .set_class_name("accordion-content inner-accordion-content");
+
+            let clip_duration: f32 = match scene_value.animation_player.clip_index {
+                Some(index) => scene_value.animations[index].duration,
+                None => 0.0,
+            };
+
+            // Clip
+            {
+                let clip_element: web_sys::Element =
+                    gloo::utils::document().create_element("div").unwrap();
+                clip_element.set_class_name("widget-row");
+
+                let clip_label_element: web_sys::Element =
+                    gloo::utils::document().create_element("div").unwrap();
+                clip_label_element.set_class_name("widget-label");
+                clip_label_element.set_text_content(Some("Clip"));
+
+                let clip_select_element = gloo::utils::document().create_element("select").unwrap();
+                clip_select_element.set_class_name("widget-value select-element");
+                clip_select_element.set_id("animation-clip-select");
+
+                if scene_value.animations.is_empty() {
+                    let clip_option_none =
+                        gloo::utils::document().create_element("option").unwrap();
+                    clip_option_none.set_text_content(Some("none"));
+                    clip_select_element.append_child(&clip_option_none).unwrap();
+                    clip_select_element.set_attribute("disabled", "").unwrap();
+                }
+                for (i, clip) in scene_value.animations.iter().enumerate() {
+                    let clip_option = gloo::utils::document().create_element("option").unwrap();
+                    clip_option.set_attribute("value", &i.to_string()).unwrap();
+                    clip_option.set_text_content(Some(
+                        clip.name
+                            .clone()
+                            .unwrap_or(format!("clip {}", i))
+                            .as_str(),
+                    ));
+                    clip_select_element.append_child(&clip_option).unwrap();
+                }
+
+                {
+                    let scene_clone: std::rc::Rc<std::cell::RefCell<engine::scene::Scene>> =
+                        scene.clone();
+
+                    let clip_closure: wasm_bindgen::prelude::Closure<dyn FnMut(_)> =
+                        wasm_bindgen::closure::Closure::wrap(Box::new(
+                            move |_event: web_sys::InputEvent| {
+                                let clip_element: web_sys::Element = gloo::utils::document()
+                                    .get_element_by_id("animation-clip-select")
+                                    .unwrap();
+                                let clip_element: web_sys::HtmlSelectElement =
+                                    clip_element.dyn_into().unwrap();
+                                let value: String = clip_element.value();
+
+                                let mut scene_value = scene_clone.borrow_mut();
+                                let clip_index: usize = value.parse::<usize>().unwrap();
+                                scene_value.animation_player.clip_index = Some(clip_index);
+                                scene_value.animation_player.time = 0.0;
+
+                                let duration: f32 = scene_value.animations[clip_index].duration;
+                                let time_range_element: web_sys::Element =
+                                    gloo::utils::document()
+                                        .get_element_by_id("animation-range-time")
+                                        .unwrap();
+                                time_range_element
+                                    .set_attribute("max", &duration.to_string())
+                                    .unwrap();
+                            },
+                        )
+                            as Box<dyn FnMut(_)>);
+
+                    clip_select_element
+                        .add_event_listener_with_callback(
+                            "change",
+                            clip_closure.as_ref().unchecked_ref(),
+                        )
+                        .unwrap();
+                    clip_closure.forget();
+                }
+
+                clip_element.append_child(&clip_label_element).unwrap();
+                clip_element.append_child(&clip_select_element).unwrap();
+
+                animation_accordion_content_element
+                    .append_child(&clip_element)
+                    .unwrap();
+            }
+            // Play
+            {
+                let play_element: web_sys::Element =
+                    gloo::utils::document().create_element("div").unwrap();
+                play_element.set_class_name("widget-row");
+
+                let play_label_element: web_sys::Element =
+                    gloo::utils::document().create_element("div").unwrap();
+                play_label_element.set_class_name("widget-label");
+                play_label_element.set_text_content(Some("Playback"));
+
+                let play_button_element: web_sys::Element =
+                    gloo::utils::document().create_element("button").unwrap();
+                play_button_element.set_class_name("widget-value select-element");
+                play_button_element.set_id("animation-play-button");
+                if scene_value.animation_player.is_playing {
+                    play_button_element.set_text_content(Some("pause"));
+                } else {
+                    play_button_element.set_text_content(Some("play"));
+                }
+                if scene_value.animations.is_empty() {
+                    play_button_element.set_attribute("disabled", "").unwrap();
+                }
+
+                {
+                    let scene_clone: std::rc::Rc<std::cell::RefCell<engine::scene::Scene>> =
+                        scene.clone();
+
+                    let play_closure: wasm_bindgen::prelude::Closure<dyn FnMut(_)> =
+                        wasm_bindgen::closure::Closure::wrap(Box::new(
+                            move |_event: web_sys::MouseEvent| {
+                                let mut scene_value = scene_clone.borrow_mut();
+                                let is_playing: bool = !scene_value.animation_player.is_playing;
+                                scene_value.animation_player.is_playing = is_playing;
+
+                                let play_button_element: web_sys::Element =
+                                    gloo::utils::document()
+                                        .get_element_by_id("animation-play-button")
+                                        .unwrap();
+                                if is_playing {
+                                    play_button_element.set_text_content(Some("pause"));
+                                } else {
+                                    play_button_element.set_text_content(Some("play"));
+                                }
+                            },
+                        )
+                            as Box<dyn FnMut(_)>);
+
+                    play_button_element
+                        .add_event_listener_with_callback(
+                            "click",
+                            play_closure.as_ref().unchecked_ref(),
+                        )
+                        .unwrap();
+                    play_closure.forget();
+                }
+
+                play_element.append_child(&play_label_element).unwrap();
+                play_element.append_child(&play_button_element).unwrap();
+
+                animation_accordion_content_element
+                    .append_child(&play_element)
+                    .unwrap();
+            }
+            // Time
+            {
+                let time_element: web_sys::Element =
+                    gloo::utils::document().create_element("div").unwrap();
+                time_element.set_class_name("widget-row");
+
+                let time_label_element: web_sys::Element =
+                    gloo::utils::document().create_element("div").unwrap();
+                time_label_element.set_class_name("widget-label");
+                time_label_element.set_text_content(Some("Time"));
+
+                let time_content_element = gloo::utils::document().create_element("div").unwrap();
+                time_content_element.set_class_name("widget-value");
+
+                {
+                    let time_input_range: web_sys::Element =
+                        gloo::utils::document().create_element("input").unwrap();
+                    let time_input_range: web_sys::HtmlInputElement =
+                        time_input_range.dyn_into().unwrap();
+                    time_input_range.set_id("animation-range-time");
+                    time_input_range.set_class_name("range-element");
+                    time_input_range.set_attribute("type", "range").unwrap();
+                    time_input_range.set_attribute("min", "0.0").unwrap();
+                    time_input_range
+                        .set_attribute("max", &clip_duration.to_string())
+                        .unwrap();
+                    time_input_range.set_attribute("step", "0.01").unwrap();
+                    time_input_range
+                        .set_value(scene_value.animation_player.time.to_string().as_str());
+
+                    let time_input_range_text: web_sys::Element =
+                        gloo::utils::document().create_element("div").unwrap();
+                    time_input_range_text.set_id("animation-range-time-text");
+                    time_input_range_text.set_class_name("range-text-element");
+                    time_input_range_text.set_text_content(Some(
+                        format!("{:.2}", scene_value.animation_player.time).as_str(),
+                    ));
+
+                    {
+                        let scene_clone: std::rc::Rc<std::cell::RefCell<engine::scene::Scene>> =
+                            scene.clone();
+
+                        let time_range_closure: wasm_bindgen::prelude::Closure<dyn FnMut(_)> =
+                            wasm_bindgen::closure::Closure::wrap(Box::new(
+                                move |_event: web_sys::InputEvent| {
+                                    let time_range_element: web_sys::Element =
+                                        gloo::utils::document()
+                                            .get_element_by_id("animation-range-time")
+                                            .unwrap();
+                                    let time_range_element: web_sys::HtmlInputElement =
+                                        time_range_element.dyn_into().unwrap();
+                                    let value: String = time_range_element.value();
+
+                                    // scrubbing pauses playback
+                                    let mut scene_value = scene_clone.borrow_mut();
+                                    scene_value.animation_player.time =
+                                        value.parse::<f32>().unwrap();
+                                    scene_value.animation_player.is_playing = false;
+
+                                    let time_text_element: web_sys::Element =
+                                        gloo::utils::document()
+                                            .get_element_by_id("animation-range-time-text")
+                                            .unwrap();
+                                    time_text_element.set_text_content(Some(&value));
+                                    let play_button_element: web_sys::Element =
+                                        gloo::utils::document()
+                                            .get_element_by_id("animation-play-button")
+                                            .unwrap();
+                                    play_button_element.set_text_content(Some("play"));
+                                },
+                            )
+                                as Box<dyn FnMut(_)>);
+
+                        time_input_range
+                            .add_event_listener_with_callback(
+                                "input",
+                                time_range_closure.as_ref().unchecked_ref(),
+                            )
+                            .unwrap();
+                        time_range_closure.forget();
+                    }
+
+                    time_content_element
+                        .append_child(&time_input_range)
+                        .unwrap();
+                    time_content_element
+                        .append_child(&time_input_range_text)
+                        .unwrap();
+                }
+
+                time_element.append_child(&time_label_element).unwrap();
+                time_element.append_child(&time_content_element).unwrap();
+
+                animation_accordion_content_element
+                    .append_child(&time_element)
+                    .unwrap();
+            }
+
+            accordion_content_element
+                .append_child(&animation_accordion_input_element)
+                .unwrap();
+            accordion_content_element
+                .append_child(&animation_accordion_label_element)
+                .unwrap();
+            accordion_content_element
+                .append_child(&animation_accordion_content_element)
+                .unwrap();
+        }
+
+        // morph
+        {
+            let morph_accordion_input_element =
+                gloo::utils::document().create_element("input").unwrap();
+            let morph_accordion_input_element: web_sys::HtmlInputElement =
+                morph_accordion_input_element.dyn_into().unwrap();
+            morph_accordion_input_element
+                .set_attribute("type", "checkbox")
+                .unwrap();
+            morph_accordion_input_element.set_class_name("accordion-input");
+            morph_accordion_input_element.set_id("accordion-morph");
+            morph_accordion_input_element.set_checked(true);
+
+            let morph_accordion_label_element =
+                gloo::utils::document().create_element("label").unwrap();
+            morph_accordion_label_element.set_class_name("accordion-label inner-accordion-label");
+            morph_accordion_label_element.set_text_content(Some("Morph Target"));
+            morph_accordion_label_element
+                .set_attribute("for", "accordion-morph")
+                .unwrap();
+
+            let morph_accordion_content_element =
+                gloo::utils::document().create_element("div").unwrap();
+            morph_accordion_content_element
+                .set_class_name("accordion-content inner-accordion-content");
+
+            let morph_objects: Vec<&engine::scene::SceneObject> = scene_value
+                .objects
+                .iter()
+                .filter(|object| object.morph_weights.is_empty() == false)
+                .collect::<Vec<_>>();
+
+            // Object
+            {
+                let morph_object_element: web_sys::Element =
+                    gloo::utils::document().create_element("div").unwrap();
+                morph_object_element.set_class_name("widget-row");
+
+                let morph_object_label_element: web_sys::Element =
+                    gloo::utils::document().create_element("div").unwrap();
+                morph_object_label_element.set_class_name("widget-label");
+                morph_object_label_element.set_text_content(Some("Object"));
+
+                let morph_object_select_element =
+                    gloo::utils::document().create_element("select").unwrap();
+                morph_object_select_element.set_class_name("widget-value select-element");
+                morph_object_select_element.set_id("morph-object-select");
+
+                if morph_objects.is_empty() {
+                    let morph_object_option_none =
+                        gloo::utils::document().create_element("option").unwrap();
+                    morph_object_option_none.set_text_content(Some("none"));
+                    morph_object_select_element
+                        .append_child(&morph_object_option_none)
+                        .unwrap();
+                    morph_object_select_element
+                        .set_attribute("disabled", "")
+                        .unwrap();
+                }
+                for object in morph_objects.iter() {
+                    let morph_object_option =
+                        gloo::utils::document().create_element("option").unwrap();
+                    morph_object_option
+                        .set_attribute("value", &object.index.to_string())
+                        .unwrap();
+                    morph_object_option.set_text_content(Some(
+                        object
+                            ._name
+                            .clone()
+                            .unwrap_or(format!("node {}", object.index))
+                            .as_str(),
+                    ));
+                    morph_object_select_element
+                        .append_child(&morph_object_option)
+                        .unwrap();
+                }
+
+                {
+                    let scene_clone: std::rc::Rc<std::cell::RefCell<engine::scene::Scene>> =
+                        scene.clone();
+
+                    let morph_object_closure: wasm_bindgen::prelude::Closure<dyn FnMut(_)> =
+                        wasm_bindgen::closure::Closure::wrap(Box::new(
+                            move |_event: web_sys::InputEvent| {
+                                let morph_object_element: web_sys::Element =
+                                    gloo::utils::document()
+                                        .get_element_by_id("morph-object-select")
+                                        .unwrap();
+                                let morph_object_element: web_sys::HtmlSelectElement =
+                                    morph_object_element.dyn_into().unwrap();
+                                let value: String = morph_object_element.value();
+
+                                create_morph_weight_rows(
+                                    &scene_clone,
+                                    value.parse::<usize>().unwrap(),
+                                );
+                            },
+                        )
+                            as Box<dyn FnMut(_)>);
+
+                    morph_object_select_element
+                        .add_event_listener_with_callback(
+                            "change",
+                            morph_object_closure.as_ref().unchecked_ref(),
+                        )
+                        .unwrap();
+                    morph_object_closure.forget();
+                }
+
+                morph_object_element
+                    .append_child(&morph_object_label_element)
+                    .unwrap();
+                morph_object_element
+                    .append_child(&morph_object_select_element)
+                    .unwrap();
+
+                morph_accordion_content_element
+                    .append_child(&morph_object_element)
+                    .unwrap();
+            }
+            // Weights, rebuilt for the selected object
+            {
+                let morph_weight_list_element: web_sys::Element =
+                    gloo::utils::document().create_element("div").unwrap();
+                morph_weight_list_element.set_id("morph-weight-list");
+
+                morph_accordion_content_element
+                    .append_child(&morph_weight_list_element)
+                    .unwrap();
+            }
+
+            accordion_content_element
+                .append_child(&morph_accordion_input_element)
+                .unwrap();
+            accordion_content_element
+                .append_child(&morph_accordion_label_element)
+                .unwrap();
+            accordion_content_element
+                .append_child(&morph_accordion_content_element)
+                .unwrap();
+        }
+
         view_graphics
             .append_child(&accordion_input_element)
             .unwrap();
@@ -951,6 +1566,138 @@ fn create_view_dialog(scene: &std::rc::Rc<std::cell::RefCell<engine::scene::Scen
     }
 
     body.append_child(&view_wrapper).unwrap();
+
+    let first_morph_object: Option<usize> = scene_value
+        .objects
+        .iter()
+        .position(|object| object.morph_weights.is_empty() == false);
+    drop(scene_value);
+    if let Some(object_index) = first_morph_object {
+        create_morph_weight_rows(scene, object_index);
+    }
+}
+
+// Rebuild the view dialog for a switched scene
+fn refresh_view_dialog(scene: &std::rc::Rc<std::cell::RefCell<engine::scene::Scene>>) {
+    let view_wrapper: Option<web_sys::Element> =
+        gloo::utils::document().get_element_by_id("view-wrapper");
+    if let Some(view_wrapper) = view_wrapper {
+        view_wrapper.remove();
+    }
+    create_view_dialog(scene);
+}
+
+// Follow animation time while playing
+pub fn update_animation_timeline(scene: &std::rc::Rc<std::cell::RefCell<engine::scene::Scene>>) {
+    let scene_value = scene.borrow();
+    if scene_value.animation_player.is_playing == false {
+        return;
+    }
+
+    let time: f32 = scene_value.animation_player.time;
+    let time_range_element: Option<web_sys::Element> =
+        gloo::utils::document().get_element_by_id("animation-range-time");
+    if time_range_element.is_none() {
+        return;
+    }
+    let time_range_element: web_sys::HtmlInputElement =
+        time_range_element.unwrap().dyn_into().unwrap();
+    time_range_element.set_value(time.to_string().as_str());
+
+    let time_text_element: web_sys::Element = gloo::utils::document()
+        .get_element_by_id("animation-range-time-text")
+        .unwrap();
+    time_text_element.set_text_content(Some(format!("{:.2}", time).as_str()));
+}
+
+fn create_morph_weight_rows(
+    scene: &std::rc::Rc<std::cell::RefCell<engine::scene::Scene>>,
+    object_index: usize,
+) {
+    let morph_weight_list_element: web_sys::Element = gloo::utils::document()
+        .get_element_by_id("morph-weight-list")
+        .unwrap();
+    morph_weight_list_element.set_inner_html("");
+
+    let morph_weights: Vec<f32> = scene.borrow().objects[object_index].morph_weights.clone();
+    for (i, weight) in morph_weights.iter().enumerate() {
+        let weight_element: web_sys::Element =
+            gloo::utils::document().create_element("div").unwrap();
+        weight_element.set_class_name("widget-row");
+
+        let weight_label_element: web_sys::Element =
+            gloo::utils::document().create_element("div").unwrap();
+        weight_label_element.set_class_name("widget-label");
+        weight_label_element.set_text_content(Some(format!("Weight {}", i).as_str()));
+
+        let weight_content_element = gloo::utils::document().create_element("div").unwrap();
+        weight_content_element.set_class_name("widget-value");
+
+        let range_id: String = format!("morph-range-{}", i);
+        let range_text_id: String = format!("morph-range-{}-text", i);
+
+        let weight_input_range: web_sys::Element =
+            gloo::utils::document().create_element("input").unwrap();
+        let weight_input_range: web_sys::HtmlInputElement = weight_input_range.dyn_into().unwrap();
+        weight_input_range.set_id(&range_id);
+        weight_input_range.set_class_name("range-element");
+        weight_input_range.set_attribute("type", "range").unwrap();
+        weight_input_range.set_attribute("min", "0.0").unwrap();
+        weight_input_range.set_attribute("max", "1.0").unwrap();
+        weight_input_range.set_attribute("step", "0.01").unwrap();
+        weight_input_range.set_value(weight.to_string().as_str());
+
+        let weight_input_range_text: web_sys::Element =
+            gloo::utils::document().create_element("div").unwrap();
+        weight_input_range_text.set_id(&range_text_id);
+        weight_input_range_text.set_class_name("range-text-element");
+        weight_input_range_text.set_text_content(Some(format!("{:.2}", weight).as_str()));
+
+        {
+            let scene_clone: std::rc::Rc<std::cell::RefCell<engine::scene::Scene>> = scene.clone();
+
+            let weight_range_closure: wasm_bindgen::prelude::Closure<dyn FnMut(_)> =
+                wasm_bindgen::closure::Closure::wrap(Box::new(move |_event: web_sys::InputEvent| {
+                    let weight_range_element: web_sys::Element = gloo::utils::document()
+                        .get_element_by_id(&range_id)
+                        .unwrap();
+                    let weight_range_element: web_sys::HtmlInputElement =
+                        weight_range_element.dyn_into().unwrap();
+                    let value: String = weight_range_element.value();
+
+                    let mut scene_value = scene_clone.borrow_mut();
+                    scene_value.objects[object_index].morph_weights[i] =
+                        value.parse::<f32>().unwrap();
+
+                    let weight_text_element: web_sys::Element = gloo::utils::document()
+                        .get_element_by_id(&range_text_id)
+                        .unwrap();
+                    weight_text_element.set_text_content(Some(&value));
+                }) as Box<dyn FnMut(_)>);
+
+            weight_input_range
+                .add_event_listener_with_callback(
+                    "input",
+                    weight_range_closure.as_ref().unchecked_ref(),
+                )
+                .unwrap();
+            weight_range_closure.forget();
+        }
+
+        weight_content_element
+            .append_child(&weight_input_range)
+            .unwrap();
+        weight_content_element
+            .append_child(&weight_input_range_text)
+            .unwrap();
+
+        weight_element.append_child(&weight_label_element).unwrap();
+        weight_element.append_child(&weight_content_element).unwrap();
+
+        morph_weight_list_element
+            .append_child(&weight_element)
+            .unwrap();
+    }
 }
 
 fn reset_state() {
@@ -21,11 +21,14 @@ pub async fn main() {
         std::rc::Rc::new(std::cell::RefCell::new(scene));
 
     // Load .gltf file
-    (scene.borrow_mut().objects, scene.borrow_mut().materials) =
-        engine::load::load_gltf_scene(engine::define::GLTF_LOGO_PATH).await;
-
-    // Batch objects
-    engine::scene::batch_objects(&scene);
+    let load_options: engine::load::LoadOptions = engine::load::LoadOptions::default();
+    if let Err(error) =
+        engine::load::load_gltf_into_scene(&scene, engine::define::GLTF_LOGO_PATH, &load_options)
+            .await
+    {
+        log::error!("{}", error);
+        frontend::gui::show_error_message(&error.to_string());
+    }
 
     // Rendering context
     let webgpu_interface: rendering::webgpu::WebGPUInterface =
@@ -50,6 +53,15 @@ pub async fn main() {
     *g.borrow_mut() = Some(wasm_bindgen::closure::Closure::wrap(Box::new(move || {
         engine::scene::update_control(&scene, &control_response_js);
 
+        // Animation
+        let timestamp: f64 = web_sys::window()
+            .expect("should have a Window")
+            .performance()
+            .expect("should have a Performance")
+            .now();
+        engine::animation::update_animation(&scene, timestamp);
+        frontend::gui::update_animation_timeline(&scene);
+
         let shading_type: engine::scene::ShadingType = scene.borrow().scene_shading_type;
 
         match shading_type {
@@ -2,10 +2,37 @@
 #[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
 pub struct Vertex {
     pub pos: [f32; 4],
-    pub color: [f32; 3],
+    pub color: [f32; 4],
     pub uv: [f32; 2],
     pub normal: [f32; 3],
-    pub tangent: [f32; 3],
+    pub tangent: [f32; 4],
+    pub joints: [u32; 4],
+    pub weights: [f32; 4],
+    pub uv1: [f32; 2],
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Topology {
+    #[default]
+    Triangles,
+    Lines,
+    Points,
+}
+
+#[derive(Clone, Default)]
+pub struct SubMesh {
+    pub index_offset: u32,
+    pub index_count: u32,
+    pub material: Option<u32>,
+    pub topology: Topology,
+}
+
+// Per vertex displacements of one blend shape, same length as Mesh.vertices
+#[derive(Clone, Default)]
+pub struct MorphTarget {
+    pub positions: Vec<[f32; 3]>,
+    pub normals: Vec<[f32; 3]>,
+    pub tangents: Vec<[f32; 3]>,
 }
 
 #[derive(Clone, Default)]
@@ -13,5 +40,6 @@ pub struct Mesh {
     pub _name: std::string::String,
     pub vertices: Vec<Vertex>,
     pub indices: Vec<u32>,
-    pub material: Option<u32>,
+    pub submeshes: Vec<SubMesh>,
+    pub morph_targets: Vec<MorphTarget>,
 }
@@ -15,6 +15,14 @@ pub struct WebGPUInterface<'a> {
     pub queue: wgpu::Queue,
     pub swapchain_format: wgpu::TextureFormat,
     pub depth_texture: wgpu::Texture,
+    pub sampler_cache: std::cell::RefCell<
+        std::collections::HashMap<engine::scene::SceneSampler, std::rc::Rc<wgpu::Sampler>>,
+    >,
+    // texture views of the materials of texture_cache_path, keyed by (material, texture label)
+    pub texture_cache: std::cell::RefCell<
+        std::collections::HashMap<(u32, &'static str), std::rc::Rc<wgpu::TextureView>>,
+    >,
+    pub texture_cache_path: std::cell::RefCell<String>,
 }
 
 pub struct WebGPURenderResource {
@@ -24,19 +32,32 @@ pub struct WebGPURenderResource {
     pub index_count: u32,
     pub bind_group: wgpu::BindGroup,
     pub _bind_group_layout: wgpu::BindGroupLayout,
-    pub bind_group_2: Option<wgpu::BindGroup>,
+    pub submeshes: Vec<WebGPUSubMeshResource>,
     pub uniform_buf: wgpu::Buffer,
-    pub render_pipeline: wgpu::RenderPipeline,
+    pub render_pipeline: std::rc::Rc<wgpu::RenderPipeline>,
+    pub joint_buf: Option<wgpu::Buffer>,
+    pub morph_weight_buf: Option<wgpu::Buffer>,
+}
+
+pub struct WebGPUSubMeshResource {
+    pub index_start: u32,
+    pub index_count: u32,
+    pub bind_group: wgpu::BindGroup,
+    pub pipeline: std::rc::Rc<wgpu::RenderPipeline>,
+    pub is_blend: bool,
+    pub center: [f32; 3],
 }
 
 pub struct WebGPUDifferedResource {
     pub _shader: wgpu::ShaderModule,
-    gbuffer_position_texture: wgpu::Texture,
     gbuffer_normal_texture: wgpu::Texture,
     gbuffer_albedo_texture: wgpu::Texture,
     gbuffer_metallic_roughness_texture: wgpu::Texture,
+    gbuffer_emissive_texture: wgpu::Texture,
     pub bind_groups: Vec<wgpu::BindGroup>,
     pub uniform_buf: wgpu::Buffer,
+    pub light_buf: wgpu::Buffer,
+    pub material_buf: wgpu::Buffer,
     pub render_pipeline: wgpu::RenderPipeline,
     pub debug_pipeline: wgpu::RenderPipeline,
 }
@@ -123,6 +144,9 @@ pub async fn init_interface<'a>() -> WebGPUInterface<'a> {
         queue,
         swapchain_format,
         depth_texture,
+        sampler_cache: std::cell::RefCell::new(std::collections::HashMap::new()),
+        texture_cache: std::cell::RefCell::new(std::collections::HashMap::new()),
+        texture_cache_path: std::cell::RefCell::new(String::new()),
     };
 
     return resource;
@@ -149,6 +173,7 @@ pub fn init_differed_gbuffer_pipeline(
 ) {
     struct InitMap {
         index: usize,
+        is_batched: bool,
         resource: WebGPURenderResource,
     }
 
@@ -156,31 +181,50 @@ pub fn init_differed_gbuffer_pipeline(
     let mut init_list: Vec<InitMap> = Vec::new();
     {
         let scene_borrow = scene.borrow();
+
+        // material indices only stay valid within one .gltf, a scene switch keeps the textures
+        let mut texture_cache_path = interface.texture_cache_path.borrow_mut();
+        if *texture_cache_path != scene_borrow.gltf_path {
+            interface.texture_cache.borrow_mut().clear();
+            *texture_cache_path = scene_borrow.gltf_path.clone();
+        }
+        drop(texture_cache_path);
+
         let scene_mterials = &scene_borrow.materials;
-        if scene_borrow.use_batched == false {
-            for i in 0..scene_borrow.objects.len() {
-                let object_borrow = scene_borrow.objects.get(i).unwrap();
-                if object_borrow.shading_type != 0 && object_borrow.source_mesh.is_some() {
-                    init_list.push(InitMap {
-                        index: i,
-                        resource: init_differed_gbuffers_shading(
-                            &interface,
-                            &object_borrow.source_mesh.as_ref().unwrap().borrow(),
-                            &scene_mterials,
-                        ),
-                    });
-                }
+        for i in 0..scene_borrow.objects.len() {
+            let object_borrow = scene_borrow.objects.get(i).unwrap();
+            // batched objects replace every object they were built from
+            if scene_borrow.use_batched && scene_borrow.is_batched(object_borrow) {
+                continue;
             }
-        } else {
+            if object_borrow.shading_type != 0 && object_borrow.source_mesh.is_some() {
+                let joint_count: usize = object_borrow
+                    .skin
+                    .map_or(0, |skin| scene_borrow.skins[skin as usize].joints.len());
+                init_list.push(InitMap {
+                    index: i,
+                    is_batched: false,
+                    resource: init_differed_gbuffers_shading(
+                        &interface,
+                        &object_borrow.source_mesh.as_ref().unwrap().borrow(),
+                        &scene_mterials,
+                        joint_count,
+                    ),
+                });
+            }
+        }
+        if scene_borrow.use_batched {
             for i in 0..scene_borrow.batched_objects.len() {
                 let batched = scene_borrow.batched_objects.get(i).unwrap();
                 if batched.shading_type != 0 && batched.source_mesh.is_some() {
                     init_list.push(InitMap {
                         index: i,
+                        is_batched: true,
                         resource: init_differed_gbuffers_shading(
                             &interface,
                             &batched.source_mesh.as_ref().unwrap().borrow(),
                             &scene_mterials,
+                            0,
                         ),
                     });
                 }
@@ -191,7 +235,7 @@ pub fn init_differed_gbuffer_pipeline(
     // Initialize pipeline
     for init_elem in init_list {
         let mut scene_borrow = scene.borrow_mut();
-        if scene_borrow.use_batched == false {
+        if init_elem.is_batched == false {
             let object_borrow = scene_borrow.objects.get_mut(init_elem.index).unwrap();
             object_borrow.shading_type = 0;
             object_borrow.render_resource = Some(std::rc::Rc::new(std::cell::RefCell::new(
@@ -229,17 +273,9 @@ pub fn update_differed_shading(
     differed_resource: &WebGPUDifferedResource,
 ) {
     // Update gbuffer
-    if scene.borrow().use_batched == false {
-        for scene_object in &scene.as_ref().borrow().objects {
-            if scene_object.shading_type == 0 {
-                update_differed_gbuffers_shading(&scene, &interface, &scene_object);
-            }
-        }
-    } else {
-        for batched in &scene.as_ref().borrow().batched_objects {
-            if batched.shading_type == 0 {
-                update_differed_gbuffers_shading(&scene, &interface, &batched);
-            }
+    for scene_object in scene.borrow().get_render_objects() {
+        if scene_object.shading_type == 0 {
+            update_differed_gbuffers_shading(&scene, &interface, &scene_object);
         }
     }
     // Update differed
@@ -315,49 +351,30 @@ pub fn render_forward_shading_main(
 
         for object in scene.borrow().objects.iter() {
             if object.shading_type == 1 {
-                rpass.set_pipeline(
-                    &object
-                        .render_resource
-                        .as_ref()
-                        .unwrap()
-                        .borrow()
-                        .render_pipeline,
-                );
-                rpass.set_bind_group(
-                    0,
-                    &object.render_resource.as_ref().unwrap().borrow().bind_group,
-                    &[],
-                );
+                let render_resource = object.render_resource.as_ref().unwrap().borrow();
                 rpass.set_index_buffer(
-                    object
-                        .render_resource
-                        .as_ref()
-                        .unwrap()
-                        .borrow()
-                        .index_buf
-                        .slice(..),
+                    render_resource.index_buf.slice(..),
                     wgpu::IndexFormat::Uint32,
                 );
-                rpass.set_vertex_buffer(
-                    0,
-                    object
-                        .render_resource
-                        .as_ref()
-                        .unwrap()
-                        .borrow()
-                        .vertex_buf
-                        .slice(..),
-                );
-                rpass.draw_indexed(
-                    0..object
-                        .render_resource
-                        .as_ref()
-                        .unwrap()
-                        .borrow()
-                        .index_count,
-                    0,
-                    0..1,
-                );
+                rpass.set_vertex_buffer(0, render_resource.vertex_buf.slice(..));
+
+                // resources without submeshes draw the whole mesh as triangles
+                if render_resource.submeshes.is_empty() {
+                    rpass.set_pipeline(&render_resource.render_pipeline);
+                    rpass.set_bind_group(0, &render_resource.bind_group, &[]);
+                    rpass.draw_indexed(0..render_resource.index_count, 0, 0..1);
+                }
+
+                // lines and points are drawn with their own pipelines
+                for submesh in render_resource.submeshes.iter() {
+                    rpass.set_pipeline(&submesh.pipeline);
+                    rpass.set_bind_group(0, &submesh.bind_group, &[]);
+                    rpass.draw_indexed(
+                        submesh.index_start..submesh.index_start + submesh.index_count,
+                        0,
+                        0..1,
+                    );
+                }
             }
         }
     }
@@ -395,14 +412,14 @@ pub fn render_differed_shading_main(
                 color_attachments: &[
                     Some(wgpu::RenderPassColorAttachment {
                         view: &differed_resource
-                            .gbuffer_position_texture
+                            .gbuffer_normal_texture
                             .create_view(&TextureViewDescriptor::default()),
                         resolve_target: None,
                         ops: wgpu::Operations {
                             load: wgpu::LoadOp::Clear(wgpu::Color {
                                 r: 0.0,
                                 g: 0.0,
-                                b: 0.0,
+                                b: 1.0,
                                 a: 1.0,
                             }),
                             store: wgpu::StoreOp::Store,
@@ -410,14 +427,14 @@ pub fn render_differed_shading_main(
                     }),
                     Some(wgpu::RenderPassColorAttachment {
                         view: &differed_resource
-                            .gbuffer_normal_texture
+                            .gbuffer_albedo_texture
                             .create_view(&TextureViewDescriptor::default()),
                         resolve_target: None,
                         ops: wgpu::Operations {
                             load: wgpu::LoadOp::Clear(wgpu::Color {
                                 r: 0.0,
                                 g: 0.0,
-                                b: 1.0,
+                                b: 0.0,
                                 a: 1.0,
                             }),
                             store: wgpu::StoreOp::Store,
@@ -425,7 +442,7 @@ pub fn render_differed_shading_main(
                     }),
                     Some(wgpu::RenderPassColorAttachment {
                         view: &differed_resource
-                            .gbuffer_albedo_texture
+                            .gbuffer_metallic_roughness_texture
                             .create_view(&TextureViewDescriptor::default()),
                         resolve_target: None,
                         ops: wgpu::Operations {
@@ -440,7 +457,7 @@ pub fn render_differed_shading_main(
                     }),
                     Some(wgpu::RenderPassColorAttachment {
                         view: &differed_resource
-                            .gbuffer_metallic_roughness_texture
+                            .gbuffer_emissive_texture
                             .create_view(&TextureViewDescriptor::default()),
                         resolve_target: None,
                         ops: wgpu::Operations {
@@ -468,144 +485,10 @@ pub fn render_differed_shading_main(
                 occlusion_query_set: None,
             });
 
-        if scene.borrow().use_batched == false {
-            for object in scene.borrow().objects.iter() {
-                if object.shading_type == 0 {
-                    gbuffer_pass.set_pipeline(
-                        &object
-                            .render_resource
-                            .as_ref()
-                            .unwrap()
-                            .borrow()
-                            .render_pipeline,
-                    );
-                    gbuffer_pass.set_bind_group(
-                        0,
-                        &object.render_resource.as_ref().unwrap().borrow().bind_group,
-                        &[],
-                    );
-                    if object
-                        .render_resource
-                        .as_ref()
-                        .unwrap()
-                        .borrow()
-                        .bind_group_2
-                        .is_some()
-                    {
-                        gbuffer_pass.set_bind_group(
-                            1,
-                            &object
-                                .render_resource
-                                .as_ref()
-                                .unwrap()
-                                .borrow()
-                                .bind_group_2,
-                            &[],
-                        );
-                    }
-                    gbuffer_pass.set_index_buffer(
-                        object
-                            .render_resource
-                            .as_ref()
-                            .unwrap()
-                            .borrow()
-                            .index_buf
-                            .slice(..),
-                        wgpu::IndexFormat::Uint32,
-                    );
-                    gbuffer_pass.set_vertex_buffer(
-                        0,
-                        object
-                            .render_resource
-                            .as_ref()
-                            .unwrap()
-                            .borrow()
-                            .vertex_buf
-                            .slice(..),
-                    );
-                    gbuffer_pass.draw_indexed(
-                        0..object
-                            .render_resource
-                            .as_ref()
-                            .unwrap()
-                            .borrow()
-                            .index_count,
-                        0,
-                        0..1,
-                    );
-                }
-            }
-        } else {
-            for batched in scene.borrow().batched_objects.iter() {
-                if batched.shading_type == 0 {
-                    gbuffer_pass.set_pipeline(
-                        &batched
-                            .render_resource
-                            .as_ref()
-                            .unwrap()
-                            .borrow()
-                            .render_pipeline,
-                    );
-                    gbuffer_pass.set_bind_group(
-                        0,
-                        &batched
-                            .render_resource
-                            .as_ref()
-                            .unwrap()
-                            .borrow()
-                            .bind_group,
-                        &[],
-                    );
-                    if batched
-                        .render_resource
-                        .as_ref()
-                        .unwrap()
-                        .borrow()
-                        .bind_group_2
-                        .is_some()
-                    {
-                        gbuffer_pass.set_bind_group(
-                            1,
-                            &batched
-                                .render_resource
-                                .as_ref()
-                                .unwrap()
-                                .borrow()
-                                .bind_group_2,
-                            &[],
-                        );
-                    }
-                    gbuffer_pass.set_index_buffer(
-                        batched
-                            .render_resource
-                            .as_ref()
-                            .unwrap()
-                            .borrow()
-                            .index_buf
-                            .slice(..),
-                        wgpu::IndexFormat::Uint32,
-                    );
-                    gbuffer_pass.set_vertex_buffer(
-                        0,
-                        batched
-                            .render_resource
-                            .as_ref()
-                            .unwrap()
-                            .borrow()
-                            .vertex_buf
-                            .slice(..),
-                    );
-                    gbuffer_pass.draw_indexed(
-                        0..batched
-                            .render_resource
-                            .as_ref()
-                            .unwrap()
-                            .borrow()
-                            .index_count,
-                        0,
-                        0..1,
-                    );
-                }
+        let scene_value = scene.borrow();
+        for object in scene_value.get_render_objects() {
+            if object.shading_type == 0 {
+                draw_gbuffers_object(&mut gbuffer_pass, object);
             }
         }
     }
@@ -646,6 +529,43 @@ pub fn render_differed_shading_main(
         differed_pass.draw(0..6, 0..1);
     }
 
+    // blend pass
+    {
+        let scene_value = scene.borrow();
+
+        let mut blend_pass: wgpu::RenderPass<'_> =
+            encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Blend render pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Load,
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                    view: &interface
+                        .depth_texture
+                        .create_view(&wgpu::TextureViewDescriptor::default()),
+                    depth_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Load,
+                        store: wgpu::StoreOp::Store,
+                    }),
+                    stencil_ops: None,
+                }),
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+
+        draw_blend_objects(
+            &mut blend_pass,
+            &scene_value,
+            &scene_value.get_render_objects(),
+            &differed_resource.bind_groups[1],
+        );
+    }
+
     interface.queue.submit(Some(encoder.finish()));
     frame.present();
 }
@@ -797,9 +717,11 @@ fn init_color_shading(interface: &WebGPUInterface, mesh: &common::Mesh) -> WebGP
         index_count,
         bind_group,
         _bind_group_layout: bind_group_layout,
-        bind_group_2: None,
+        submeshes: Vec::new(),
         uniform_buf,
-        render_pipeline,
+        render_pipeline: std::rc::Rc::new(render_pipeline),
+        joint_buf: None,
+        morph_weight_buf: None,
     };
 
     return render_resource;
@@ -826,8 +748,7 @@ fn update_color_shading(
 
     // Create matrices and write buffer
     let view_matrix = glam::Mat4::look_to_rh(eye, direction, glam::Vec3::Z);
-    let projection_matrix: glam::Mat4 =
-        glam::Mat4::perspective_rh(std::f32::consts::FRAC_PI_4, aspect_ratio, 0.01, 100.0);
+    let projection_matrix: glam::Mat4 = scene_value.get_projection_matrix(aspect_ratio);
     let mx_total: glam::Mat4 = projection_matrix * view_matrix;
     let mx_ref: &[f32; 16] = mx_total.as_ref();
     interface
@@ -903,7 +824,7 @@ fn init_phong_shading(interface: &WebGPUInterface, mesh: &common::Mesh) -> WebGP
                 }],
             });
 
-    let bind_group: wgpu::BindGroup =
+    let create_bind_group = || -> wgpu::BindGroup {
         interface
             .device
             .create_bind_group(&wgpu::BindGroupDescriptor {
@@ -913,7 +834,9 @@ fn init_phong_shading(interface: &WebGPUInterface, mesh: &common::Mesh) -> WebGP
                     resource: uniform_buf.as_entire_binding(),
                 }],
                 label: Some("Bind group 0"),
-            });
+            })
+    };
+    let bind_group: wgpu::BindGroup = create_bind_group();
 
     // pipeline
 
@@ -937,13 +860,14 @@ fn init_phong_shading(interface: &WebGPUInterface, mesh: &common::Mesh) -> WebGP
             },
             wgpu::VertexAttribute {
                 format: wgpu::VertexFormat::Float32x3,
-                offset: std::mem::size_of::<[f32; 9]>() as u64,
+                offset: std::mem::size_of::<[f32; 10]>() as u64,
                 shader_location: 1,
             },
         ],
     }];
 
-    let render_pipeline: wgpu::RenderPipeline =
+    // lines and points have no faces to cull
+    let create_pipeline = |topology: common::Topology| -> wgpu::RenderPipeline {
         interface
             .device
             .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
@@ -962,8 +886,13 @@ fn init_phong_shading(interface: &WebGPUInterface, mesh: &common::Mesh) -> WebGP
                     targets: &[Some(interface.swapchain_format.into())],
                 }),
                 primitive: wgpu::PrimitiveState {
+                    topology: get_primitive_topology(topology),
                     front_face: WEBGPU_FRONT_FACE,
-                    cull_mode: Some(WEBGPU_CULL_MODE),
+                    cull_mode: if topology == common::Topology::Triangles {
+                        Some(WEBGPU_CULL_MODE)
+                    } else {
+                        None
+                    },
                     ..Default::default()
                 },
                 depth_stencil: Some(wgpu::DepthStencilState {
@@ -976,7 +905,33 @@ fn init_phong_shading(interface: &WebGPUInterface, mesh: &common::Mesh) -> WebGP
                 multisample: wgpu::MultisampleState::default(),
                 multiview: None,
                 cache: None,
-            });
+            })
+    };
+    let render_pipeline: std::rc::Rc<wgpu::RenderPipeline> =
+        std::rc::Rc::new(create_pipeline(common::Topology::Triangles));
+
+    // One draw per submesh with the pipeline of its topology
+    let mut pipelines: std::collections::HashMap<
+        common::Topology,
+        std::rc::Rc<wgpu::RenderPipeline>,
+    > = std::collections::HashMap::new();
+    pipelines.insert(common::Topology::Triangles, render_pipeline.clone());
+
+    let mut submeshes: Vec<WebGPUSubMeshResource> = Vec::with_capacity(mesh.submeshes.len());
+    for submesh in mesh.submeshes.iter() {
+        let pipeline: std::rc::Rc<wgpu::RenderPipeline> = pipelines
+            .entry(submesh.topology)
+            .or_insert_with(|| std::rc::Rc::new(create_pipeline(submesh.topology)))
+            .clone();
+        submeshes.push(WebGPUSubMeshResource {
+            index_start: submesh.index_offset,
+            index_count: submesh.index_count,
+            bind_group: create_bind_group(),
+            pipeline: pipeline,
+            is_blend: false,
+            center: [0.0, 0.0, 0.0],
+        });
+    }
 
     let index_count: u32 = index_data.len() as u32;
 
@@ -987,9 +942,11 @@ fn init_phong_shading(interface: &WebGPUInterface, mesh: &common::Mesh) -> WebGP
         index_count,
         bind_group,
         _bind_group_layout: bind_group_layout,
-        bind_group_2: None,
+        submeshes,
         uniform_buf,
         render_pipeline,
+        joint_buf: None,
+        morph_weight_buf: None,
     };
 
     return render_resource;
@@ -1024,8 +981,7 @@ fn update_phong_shading(
     }
 
     let view_matrix = glam::Mat4::look_to_rh(eye, direction, glam::Vec3::Z);
-    let projection_matrix: glam::Mat4 =
-        glam::Mat4::perspective_rh(std::f32::consts::FRAC_PI_4, aspect_ratio, 0.01, 100.0);
+    let projection_matrix: glam::Mat4 = scene_value.get_projection_matrix(aspect_ratio);
     let transform_matrix: glam::Mat4 = projection_matrix * view_matrix * model_matrix;
 
     let directional: [f32; 3] = scene_value.directional_light_angle;
@@ -1062,6 +1018,7 @@ fn init_differed_gbuffers_shading(
     interface: &WebGPUInterface,
     mesh: &common::Mesh,
     materials: &Vec<engine::scene::SceneMaterial>,
+    joint_count: usize,
 ) -> WebGPURenderResource {
     struct WriteGBuffersUniform {
         _model_matrix: [f32; 16],
@@ -1074,8 +1031,9 @@ fn init_differed_gbuffers_shading(
             .device
             .create_shader_module(wgpu::ShaderModuleDescriptor {
                 label: None,
-                source: wgpu::ShaderSource::Wgsl(std::borrow::Cow::Borrowed(include_str!(
-                    "../shader/differed_write_gbuffers.wgsl"
+                source: wgpu::ShaderSource::Wgsl(std::borrow::Cow::Borrowed(concat!(
+                    include_str!("../shader/mesh.wgsl"),
+                    include_str!("../shader/differed_write_gbuffers.wgsl")
                 ))),
             });
 
@@ -1101,177 +1059,6 @@ fn init_differed_gbuffers_shading(
                 usage: wgpu::BufferUsages::INDEX,
             });
 
-    // Textures : warning write texture is slow
-
-    let base_color_texture_raw = &materials
-        .get(mesh.material.unwrap() as usize)
-        .unwrap()
-        .base_color_texture;
-    let base_color_texture_size = &materials
-        .get(mesh.material.unwrap() as usize)
-        .unwrap()
-        .base_color_texture_size;
-    let base_color_texture: wgpu::Texture =
-        interface.device.create_texture(&wgpu::TextureDescriptor {
-            label: Some("base color texture"),
-            size: wgpu::Extent3d {
-                width: base_color_texture_size[0],
-                height: base_color_texture_size[1],
-                depth_or_array_layers: 1,
-            },
-            mip_level_count: 1,
-            sample_count: 1,
-            dimension: wgpu::TextureDimension::D2,
-            format: wgpu::TextureFormat::Rgba8UnormSrgb,
-            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
-            view_formats: &[],
-        });
-
-    interface.queue.write_texture(
-        wgpu::ImageCopyTexture {
-            aspect: wgpu::TextureAspect::All,
-            texture: &base_color_texture,
-            mip_level: 0,
-            origin: wgpu::Origin3d::ZERO,
-        },
-        &base_color_texture_raw,
-        wgpu::ImageDataLayout {
-            offset: 0,
-            bytes_per_row: Some(4 * base_color_texture_size[0]),
-            rows_per_image: Some(base_color_texture_size[1]),
-        },
-        wgpu::Extent3d {
-            width: base_color_texture_size[0],
-            height: base_color_texture_size[1],
-            depth_or_array_layers: 1,
-        },
-    );
-
-    let base_color_texture_view: wgpu::TextureView =
-        base_color_texture.create_view(&wgpu::TextureViewDescriptor::default());
-    let base_color_texture_sampler: wgpu::Sampler =
-        interface.device.create_sampler(&wgpu::SamplerDescriptor {
-            address_mode_u: wgpu::AddressMode::ClampToEdge,
-            address_mode_v: wgpu::AddressMode::ClampToEdge,
-            address_mode_w: wgpu::AddressMode::ClampToEdge,
-            mag_filter: wgpu::FilterMode::Linear,
-            min_filter: wgpu::FilterMode::Nearest,
-            mipmap_filter: wgpu::FilterMode::Nearest,
-            ..Default::default()
-        });
-
-    let normal_texture_raw = &materials
-        .get(mesh.material.unwrap() as usize)
-        .unwrap()
-        .normal_texture;
-    let normal_texture_size = &materials
-        .get(mesh.material.unwrap() as usize)
-        .unwrap()
-        .normal_texture_size;
-    let normal_texture: wgpu::Texture = interface.device.create_texture(&wgpu::TextureDescriptor {
-        label: Some("normal texture"),
-        size: wgpu::Extent3d {
-            width: normal_texture_size[0],
-            height: normal_texture_size[1],
-            depth_or_array_layers: 1,
-        },
-        mip_level_count: 1,
-        sample_count: 1,
-        dimension: wgpu::TextureDimension::D2,
-        format: wgpu::TextureFormat::Rgba8Unorm,
-        usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
-        view_formats: &[],
-    });
-
-    interface.queue.write_texture(
-        wgpu::ImageCopyTexture {
-            aspect: wgpu::TextureAspect::All,
-            texture: &normal_texture,
-            mip_level: 0,
-            origin: wgpu::Origin3d::ZERO,
-        },
-        &normal_texture_raw,
-        wgpu::ImageDataLayout {
-            offset: 0,
-            bytes_per_row: Some(4 * normal_texture_size[0]),
-            rows_per_image: Some(normal_texture_size[1]),
-        },
-        wgpu::Extent3d {
-            width: normal_texture_size[0],
-            height: normal_texture_size[1],
-            depth_or_array_layers: 1,
-        },
-    );
-
-    let normal_texture_view: wgpu::TextureView =
-        normal_texture.create_view(&wgpu::TextureViewDescriptor::default());
-    let normal_texture_sampler: wgpu::Sampler =
-        interface.device.create_sampler(&wgpu::SamplerDescriptor {
-            address_mode_u: wgpu::AddressMode::ClampToEdge,
-            address_mode_v: wgpu::AddressMode::ClampToEdge,
-            address_mode_w: wgpu::AddressMode::ClampToEdge,
-            mag_filter: wgpu::FilterMode::Linear,
-            min_filter: wgpu::FilterMode::Nearest,
-            mipmap_filter: wgpu::FilterMode::Nearest,
-            ..Default::default()
-        });
-
-    let metallic_texture_raw = &materials
-    .get(mesh.material.unwrap() as usize)
-    .unwrap()
-    .metallic_roughness_texture;
-    let metallic_texture_size = &materials
-        .get(mesh.material.unwrap() as usize)
-        .unwrap()
-        .metallic_roughness_texture_size;
-    let metallic_texture: wgpu::Texture = interface.device.create_texture(&wgpu::TextureDescriptor {
-        label: Some("metallic roughness texture"),
-        size: wgpu::Extent3d {
-            width: metallic_texture_size[0],
-            height: metallic_texture_size[1],
-            depth_or_array_layers: 1,
-        },
-        mip_level_count: 1,
-        sample_count: 1,
-        dimension: wgpu::TextureDimension::D2,
-        format: wgpu::TextureFormat::Rgba8Unorm,
-        usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
-        view_formats: &[],
-    });
-
-    interface.queue.write_texture(
-        wgpu::ImageCopyTexture {
-            aspect: wgpu::TextureAspect::All,
-            texture: &metallic_texture,
-            mip_level: 0,
-            origin: wgpu::Origin3d::ZERO,
-        },
-        &metallic_texture_raw,
-        wgpu::ImageDataLayout {
-            offset: 0,
-            bytes_per_row: Some(4 * metallic_texture_size[0]),
-            rows_per_image: Some(metallic_texture_size[1]),
-        },
-        wgpu::Extent3d {
-            width: metallic_texture_size[0],
-            height: metallic_texture_size[1],
-            depth_or_array_layers: 1,
-        },
-    );
-
-    let metallic_texture_view: wgpu::TextureView =
-        metallic_texture.create_view(&wgpu::TextureViewDescriptor::default());
-    let metallic_texture_sampler: wgpu::Sampler =
-        interface.device.create_sampler(&wgpu::SamplerDescriptor {
-            address_mode_u: wgpu::AddressMode::ClampToEdge,
-            address_mode_v: wgpu::AddressMode::ClampToEdge,
-            address_mode_w: wgpu::AddressMode::ClampToEdge,
-            mag_filter: wgpu::FilterMode::Linear,
-            min_filter: wgpu::FilterMode::Nearest,
-            mipmap_filter: wgpu::FilterMode::Nearest,
-            ..Default::default()
-        });
-
     // bindings
 
     let uniform_size: u64 = std::mem::size_of::<WriteGBuffersUniform>() as u64;
@@ -1282,20 +1069,123 @@ fn init_differed_gbuffers_shading(
         mapped_at_creation: false,
     });
 
-    let uniform_bind_group_layout: wgpu::BindGroupLayout = interface
-        .device
-        .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-            label: None,
-            entries: &[wgpu::BindGroupLayoutEntry {
-                binding: 0,
-                visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+    // Skinned meshes read their joint matrices from a storage buffer
+    let joint_buf: Option<wgpu::Buffer> = if joint_count > 0 {
+        Some(interface.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Joint Buffer"),
+            size: (joint_count * std::mem::size_of::<[f32; 16]>()) as u64,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        }))
+    } else {
+        None
+    };
+    // Morph targets, deltas of each target as (position, normal, tangent) per vertex
+    let morph_target_count: usize = mesh.morph_targets.len();
+    let mut morph_delta_data: Vec<[f32; 4]> =
+        Vec::with_capacity(morph_target_count * vertex_data.len() * 3);
+    for target in mesh.morph_targets.iter() {
+        for i in 0..vertex_data.len() {
+            let position: [f32; 3] = target.positions[i];
+            let normal: [f32; 3] = target.normals[i];
+            let tangent: [f32; 3] = target.tangents[i];
+            morph_delta_data.push([position[0], position[1], position[2], 0.0]);
+            morph_delta_data.push([normal[0], normal[1], normal[2], 0.0]);
+            morph_delta_data.push([tangent[0], tangent[1], tangent[2], 0.0]);
+        }
+    }
+    let morph_delta_buf: Option<wgpu::Buffer> = if morph_target_count > 0 {
+        Some(
+            interface
+                .device
+                .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: Some("Morph Delta Buffer"),
+                    contents: bytemuck::cast_slice(&morph_delta_data),
+                    usage: wgpu::BufferUsages::STORAGE,
+                }),
+        )
+    } else {
+        None
+    };
+    let morph_weight_buf: Option<wgpu::Buffer> = if morph_target_count > 0 {
+        Some(interface.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Morph Weight Buffer"),
+            size: (morph_target_count * std::mem::size_of::<f32>()) as u64,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        }))
+    } else {
+        None
+    };
+
+    let vs_entry_point: &str = match (joint_buf.is_some(), morph_weight_buf.is_some()) {
+        (true, true) => "vs_skinned_morph_main",
+        (true, false) => "vs_skinned_main",
+        (false, true) => "vs_morph_main",
+        (false, false) => define::VS_ENTRY_POINT,
+    };
+
+    let mut uniform_layout_entries: Vec<wgpu::BindGroupLayoutEntry> =
+        vec![wgpu::BindGroupLayoutEntry {
+            binding: 0,
+            visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        }];
+    let mut uniform_entries: Vec<wgpu::BindGroupEntry<'_>> = vec![wgpu::BindGroupEntry {
+        binding: 0,
+        resource: uniform_buf.as_entire_binding(),
+    }];
+    if let Some(joint_buf) = joint_buf.as_ref() {
+        uniform_layout_entries.push(wgpu::BindGroupLayoutEntry {
+            binding: 1,
+            visibility: wgpu::ShaderStages::VERTEX,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only: true },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        });
+        uniform_entries.push(wgpu::BindGroupEntry {
+            binding: 1,
+            resource: joint_buf.as_entire_binding(),
+        });
+    }
+    if let (Some(morph_delta_buf), Some(morph_weight_buf)) =
+        (morph_delta_buf.as_ref(), morph_weight_buf.as_ref())
+    {
+        for binding in [2, 3] {
+            uniform_layout_entries.push(wgpu::BindGroupLayoutEntry {
+                binding: binding,
+                visibility: wgpu::ShaderStages::VERTEX,
                 ty: wgpu::BindingType::Buffer {
-                    ty: wgpu::BufferBindingType::Uniform,
+                    ty: wgpu::BufferBindingType::Storage { read_only: true },
                     has_dynamic_offset: false,
                     min_binding_size: None,
                 },
                 count: None,
-            }],
+            });
+        }
+        uniform_entries.push(wgpu::BindGroupEntry {
+            binding: 2,
+            resource: morph_delta_buf.as_entire_binding(),
+        });
+        uniform_entries.push(wgpu::BindGroupEntry {
+            binding: 3,
+            resource: morph_weight_buf.as_entire_binding(),
+        });
+    }
+
+    let uniform_bind_group_layout: wgpu::BindGroupLayout = interface
+        .device
+        .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: None,
+            entries: &uniform_layout_entries,
         });
 
     let uniform_bind_group: wgpu::BindGroup =
@@ -1303,10 +1193,7 @@ fn init_differed_gbuffers_shading(
             .device
             .create_bind_group(&wgpu::BindGroupDescriptor {
                 layout: &uniform_bind_group_layout,
-                entries: &[wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: uniform_buf.as_entire_binding(),
-                }],
+                entries: &uniform_entries,
                 label: Some("Bind group 0"),
             });
 
@@ -1363,43 +1250,52 @@ fn init_differed_gbuffers_shading(
                         ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
                         count: None,
                     },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 6,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 7,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            multisampled: false,
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 8,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 9,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            multisampled: false,
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 10,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                        count: None,
+                    },
                 ],
                 label: Some("texture_bind_group_layout"),
             });
 
-    let texture_bind_group = interface
-        .device
-        .create_bind_group(&wgpu::BindGroupDescriptor {
-            layout: &texture_bind_group_layout,
-            entries: &[
-                wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: wgpu::BindingResource::TextureView(&base_color_texture_view),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 1,
-                    resource: wgpu::BindingResource::Sampler(&base_color_texture_sampler),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 2,
-                    resource: wgpu::BindingResource::TextureView(&normal_texture_view),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 3,
-                    resource: wgpu::BindingResource::Sampler(&normal_texture_sampler),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 4,
-                    resource: wgpu::BindingResource::TextureView(&metallic_texture_view),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 5,
-                    resource: wgpu::BindingResource::Sampler(&metallic_texture_sampler),
-                },
-            ],
-            label: Some("texture_bind_group"),
-        });
-
     // pipeline
 
     let pipeline_layout: wgpu::PipelineLayout =
@@ -1422,36 +1318,548 @@ fn init_differed_gbuffers_shading(
             },
             wgpu::VertexAttribute {
                 format: wgpu::VertexFormat::Float32x3,
-                offset: std::mem::size_of::<[f32; 9]>() as u64,
+                offset: std::mem::size_of::<[f32; 10]>() as u64,
                 shader_location: 1,
             },
             wgpu::VertexAttribute {
                 format: wgpu::VertexFormat::Float32x3,
-                offset: std::mem::size_of::<[f32; 7]>() as u64,
+                offset: std::mem::size_of::<[f32; 8]>() as u64,
                 shader_location: 2,
             },
             wgpu::VertexAttribute {
-                format: wgpu::VertexFormat::Float32x3,
-                offset: std::mem::size_of::<[f32; 12]>() as u64,
+                format: wgpu::VertexFormat::Float32x4,
+                offset: std::mem::size_of::<[f32; 13]>() as u64,
                 shader_location: 3,
             },
+            wgpu::VertexAttribute {
+                format: wgpu::VertexFormat::Uint32x4,
+                offset: std::mem::size_of::<[f32; 17]>() as u64,
+                shader_location: 4,
+            },
+            wgpu::VertexAttribute {
+                format: wgpu::VertexFormat::Float32x4,
+                offset: std::mem::size_of::<[f32; 21]>() as u64,
+                shader_location: 5,
+            },
+            wgpu::VertexAttribute {
+                format: wgpu::VertexFormat::Float32x4,
+                offset: std::mem::size_of::<[f32; 4]>() as u64,
+                shader_location: 6,
+            },
+            wgpu::VertexAttribute {
+                format: wgpu::VertexFormat::Float32x2,
+                offset: std::mem::size_of::<[f32; 25]>() as u64,
+                shader_location: 7,
+            },
         ],
     }];
 
+    let render_pipeline: std::rc::Rc<wgpu::RenderPipeline> =
+        std::rc::Rc::new(create_gbuffers_pipeline(
+            &interface,
+            &shader,
+            &pipeline_layout,
+            &vertex_buffers,
+            vs_entry_point,
+            Some(WEBGPU_CULL_MODE),
+            common::Topology::Triangles,
+        ));
+
+    // Pipelines keyed by (blend, double sided, topology), created when first needed
+    let mut pipelines: std::collections::HashMap<
+        (bool, bool, common::Topology),
+        std::rc::Rc<wgpu::RenderPipeline>,
+    > = std::collections::HashMap::new();
+    pipelines.insert(
+        (false, false, common::Topology::Triangles),
+        render_pipeline.clone(),
+    );
+
+    // Textures : warning write texture is slow
+
+    let mut submeshes: Vec<WebGPUSubMeshResource> = Vec::with_capacity(mesh.submeshes.len());
+    for submesh in mesh.submeshes.iter() {
+        let material_option: Option<&engine::scene::SceneMaterial> = submesh
+            .material
+            .and_then(|material| materials.get(material as usize));
+        if material_option.is_none() {
+            continue;
+        }
+        let material: &engine::scene::SceneMaterial = material_option.unwrap();
+
+        let base_color_texture_view: std::rc::Rc<wgpu::TextureView> = get_or_create_texture_view(
+            &interface,
+            submesh.material.unwrap_or(0),
+            "base color texture",
+            &material.base_color_texture,
+            &material.base_color_texture_size,
+            wgpu::TextureFormat::Rgba8UnormSrgb,
+        );
+        let base_color_texture_sampler: std::rc::Rc<wgpu::Sampler> =
+            get_or_create_sampler(&interface, &material.base_color_sampler);
+
+        let normal_texture_view: std::rc::Rc<wgpu::TextureView> = get_or_create_texture_view(
+            &interface,
+            submesh.material.unwrap_or(0),
+            "normal texture",
+            &material.normal_texture,
+            &material.normal_texture_size,
+            wgpu::TextureFormat::Rgba8Unorm,
+        );
+        let normal_texture_sampler: std::rc::Rc<wgpu::Sampler> =
+            get_or_create_sampler(&interface, &material.normal_sampler);
+
+        let metallic_texture_view: std::rc::Rc<wgpu::TextureView> = get_or_create_texture_view(
+            &interface,
+            submesh.material.unwrap_or(0),
+            "metallic roughness texture",
+            &material.metallic_roughness_texture,
+            &material.metallic_roughness_texture_size,
+            wgpu::TextureFormat::Rgba8Unorm,
+        );
+        let metallic_texture_sampler: std::rc::Rc<wgpu::Sampler> =
+            get_or_create_sampler(&interface, &material.metallic_roughness_sampler);
+
+        let emissive_texture_view: std::rc::Rc<wgpu::TextureView> = get_or_create_texture_view(
+            &interface,
+            submesh.material.unwrap_or(0),
+            "emissive texture",
+            &material.emissive_texture,
+            &material.emissive_texture_size,
+            wgpu::TextureFormat::Rgba8UnormSrgb,
+        );
+        let emissive_texture_sampler: std::rc::Rc<wgpu::Sampler> =
+            get_or_create_sampler(&interface, &material.emissive_sampler);
+
+        let occlusion_texture_view: std::rc::Rc<wgpu::TextureView> = get_or_create_texture_view(
+            &interface,
+            submesh.material.unwrap_or(0),
+            "occlusion texture",
+            &material.occlusion_texture,
+            &material.occlusion_texture_size,
+            wgpu::TextureFormat::Rgba8Unorm,
+        );
+        let occlusion_texture_sampler: std::rc::Rc<wgpu::Sampler> =
+            get_or_create_sampler(&interface, &material.occlusion_sampler);
+
+        // material factors
+        let alpha_cutoff: f32 = if material.alpha_mode == engine::scene::AlphaMode::Mask {
+            material.alpha_cutoff
+        } else {
+            0.0
+        };
+        let mut material_uniform: Vec<f32> = vec![
+            material.base_color_factor[0],
+            material.base_color_factor[1],
+            material.base_color_factor[2],
+            material.base_color_factor[3],
+            material.emissive_factor[0],
+            material.emissive_factor[1],
+            material.emissive_factor[2],
+            0.0,
+            material.metallic_factor,
+            material.roughness_factor,
+            material.occlusion_strength,
+            alpha_cutoff,
+            // material index, stored in the metallic gbuffer alpha
+            submesh.material.unwrap_or(0) as f32,
+            0.0,
+            0.0,
+            0.0,
+        ];
+        // texture transforms in the order of the texture bindings
+        for transform in [
+            &material.base_color_transform,
+            &material.normal_transform,
+            &material.metallic_roughness_transform,
+            &material.emissive_transform,
+            &material.occlusion_transform,
+        ] {
+            material_uniform.extend_from_slice(&get_texture_transform_data(transform));
+        }
+        let material_uniform_buf: wgpu::Buffer =
+            interface
+                .device
+                .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: Some("Material Uniform Buffer"),
+                    contents: bytemuck::cast_slice(&material_uniform),
+                    usage: wgpu::BufferUsages::UNIFORM,
+                });
+
+        let texture_bind_group = interface
+            .device
+            .create_bind_group(&wgpu::BindGroupDescriptor {
+                layout: &texture_bind_group_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(&base_color_texture_view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::Sampler(&base_color_texture_sampler),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: wgpu::BindingResource::TextureView(&normal_texture_view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 3,
+                        resource: wgpu::BindingResource::Sampler(&normal_texture_sampler),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 4,
+                        resource: wgpu::BindingResource::TextureView(&metallic_texture_view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 5,
+                        resource: wgpu::BindingResource::Sampler(&metallic_texture_sampler),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 6,
+                        resource: material_uniform_buf.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 7,
+                        resource: wgpu::BindingResource::TextureView(&emissive_texture_view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 8,
+                        resource: wgpu::BindingResource::Sampler(&emissive_texture_sampler),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 9,
+                        resource: wgpu::BindingResource::TextureView(&occlusion_texture_view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 10,
+                        resource: wgpu::BindingResource::Sampler(&occlusion_texture_sampler),
+                    },
+                ],
+                label: Some("texture_bind_group"),
+            });
+
+        let is_blend: bool = material.alpha_mode == engine::scene::AlphaMode::Blend;
+        // lines and points have no faces to cull
+        let cull_mode: Option<wgpu::Face> =
+            if material.double_sided || submesh.topology != common::Topology::Triangles {
+                None
+            } else {
+                Some(WEBGPU_CULL_MODE)
+            };
+        let pipeline: std::rc::Rc<wgpu::RenderPipeline> = pipelines
+            .entry((is_blend, material.double_sided, submesh.topology))
+            .or_insert_with(|| {
+                if is_blend {
+                    std::rc::Rc::new(create_blend_pipeline(
+                        &interface,
+                        &vertex_buffers,
+                        &uniform_bind_group_layout,
+                        &texture_bind_group_layout,
+                        vs_entry_point,
+                        cull_mode,
+                        submesh.topology,
+                    ))
+                } else {
+                    std::rc::Rc::new(create_gbuffers_pipeline(
+                        &interface,
+                        &shader,
+                        &pipeline_layout,
+                        &vertex_buffers,
+                        vs_entry_point,
+                        cull_mode,
+                        submesh.topology,
+                    ))
+                }
+            })
+            .clone();
+
+        // Center of the submesh, used to sort blended submeshes back to front
+        let mut center: glam::Vec3 = glam::Vec3::ZERO;
+        let index_range: std::ops::Range<usize> =
+            submesh.index_offset as usize..(submesh.index_offset + submesh.index_count) as usize;
+        for index in index_data[index_range].iter() {
+            let position: [f32; 4] = vertex_data[*index as usize].pos;
+            center += glam::Vec3::new(position[0], position[1], position[2]);
+        }
+        center /= submesh.index_count.max(1) as f32;
+
+        submeshes.push(WebGPUSubMeshResource {
+            index_start: submesh.index_offset,
+            index_count: submesh.index_count,
+            bind_group: texture_bind_group,
+            pipeline: pipeline,
+            is_blend: is_blend,
+            center: center.to_array(),
+        });
+    }
+
+    let index_count: u32 = index_data.len() as u32;
+
+    let render_resource: WebGPURenderResource = WebGPURenderResource {
+        _shader: shader,
+        vertex_buf,
+        index_buf,
+        index_count,
+        bind_group: uniform_bind_group,
+        _bind_group_layout: uniform_bind_group_layout,
+        submeshes,
+        uniform_buf,
+        render_pipeline,
+        joint_buf,
+        morph_weight_buf,
+    };
+
+    return render_resource;
+}
+
+// Number of levels down to 1x1
+fn get_mip_level_count(texture_size: &[u32; 2]) -> u32 {
+    return 32 - texture_size[0].max(texture_size[1]).max(1).leading_zeros();
+}
+
+// Next mip level of rgba8 data, 2x2 box filter, sRGB texels are averaged in linear space
+fn downsample_rgba8(texture_raw: &[u8], texture_size: &[u32; 2], is_srgb: bool) -> Vec<u8> {
+    let to_linear = |value: u8| -> f32 {
+        let value: f32 = value as f32 / 255.0;
+        if !is_srgb {
+            value
+        } else if value <= 0.04045 {
+            value / 12.92
+        } else {
+            ((value + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    let from_linear = |value: f32| -> u8 {
+        let value: f32 = if !is_srgb {
+            value
+        } else if value <= 0.0031308 {
+            value * 12.92
+        } else {
+            1.055 * value.powf(1.0 / 2.4) - 0.055
+        };
+        (value.clamp(0.0, 1.0) * 255.0).round() as u8
+    };
+
+    let width: usize = texture_size[0] as usize;
+    let height: usize = texture_size[1] as usize;
+    let next_width: usize = (width / 2).max(1);
+    let next_height: usize = (height / 2).max(1);
+
+    let mut out_data: Vec<u8> = Vec::with_capacity(next_width * next_height * 4);
+    for y in 0..next_height {
+        for x in 0..next_width {
+            // odd sizes clamp the second texel to the edge
+            let xs: [usize; 2] = [(x * 2).min(width - 1), (x * 2 + 1).min(width - 1)];
+            let ys: [usize; 2] = [(y * 2).min(height - 1), (y * 2 + 1).min(height - 1)];
+            for channel in 0..4 {
+                let mut sum: f32 = 0.0;
+                for sy in ys.iter() {
+                    for sx in xs.iter() {
+                        let value: u8 = texture_raw[(sy * width + sx) * 4 + channel];
+                        // alpha is always linear
+                        sum += if channel == 3 {
+                            value as f32 / 255.0
+                        } else {
+                            to_linear(value)
+                        };
+                    }
+                }
+                let average: f32 = sum / 4.0;
+                out_data.push(if channel == 3 {
+                    (average.clamp(0.0, 1.0) * 255.0).round() as u8
+                } else {
+                    from_linear(average)
+                });
+            }
+        }
+    }
+    return out_data;
+}
+
+fn create_texture_view_from_raw(
+    interface: &WebGPUInterface,
+    label: &str,
+    texture_raw: &Vec<u8>,
+    texture_size: &[u32; 2],
+    format: wgpu::TextureFormat,
+) -> wgpu::TextureView {
+    // mips are only built from complete rgba8 data
+    let is_complete: bool =
+        texture_raw.len() == 4 * texture_size[0] as usize * texture_size[1] as usize;
+    let mip_level_count: u32 = if is_complete {
+        get_mip_level_count(texture_size)
+    } else {
+        1
+    };
+    let texture: wgpu::Texture = interface.device.create_texture(&wgpu::TextureDescriptor {
+        label: Some(label),
+        size: wgpu::Extent3d {
+            width: texture_size[0],
+            height: texture_size[1],
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: mip_level_count,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: format,
+        usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+        view_formats: &[],
+    });
+
+    // Mips are built on the CPU so the sampler's mipmap filter has levels to pick from
+    let mut level_data: std::borrow::Cow<'_, [u8]> = std::borrow::Cow::Borrowed(texture_raw);
+    let mut level_size: [u32; 2] = *texture_size;
+    for mip_level in 0..mip_level_count {
+        if mip_level > 0 {
+            let next_data: Vec<u8> = downsample_rgba8(&level_data, &level_size, format.is_srgb());
+            level_data = std::borrow::Cow::Owned(next_data);
+            level_size = [(level_size[0] / 2).max(1), (level_size[1] / 2).max(1)];
+        }
+
+        interface.queue.write_texture(
+            wgpu::ImageCopyTexture {
+                aspect: wgpu::TextureAspect::All,
+                texture: &texture,
+                mip_level: mip_level,
+                origin: wgpu::Origin3d::ZERO,
+            },
+            &level_data,
+            wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(4 * level_size[0]),
+                rows_per_image: Some(level_size[1]),
+            },
+            wgpu::Extent3d {
+                width: level_size[0],
+                height: level_size[1],
+                depth_or_array_layers: 1,
+            },
+        );
+    }
+
+    return texture.create_view(&wgpu::TextureViewDescriptor::default());
+}
+
+// Instances and submeshes sharing a material upload its textures and mips once
+fn get_or_create_texture_view(
+    interface: &WebGPUInterface,
+    material_index: u32,
+    label: &'static str,
+    texture_raw: &Vec<u8>,
+    texture_size: &[u32; 2],
+    format: wgpu::TextureFormat,
+) -> std::rc::Rc<wgpu::TextureView> {
+    let mut texture_cache = interface.texture_cache.borrow_mut();
+    let texture_view = texture_cache
+        .entry((material_index, label))
+        .or_insert_with(|| {
+            std::rc::Rc::new(create_texture_view_from_raw(
+                interface,
+                label,
+                texture_raw,
+                texture_size,
+                format,
+            ))
+        });
+    return texture_view.clone();
+}
+
+fn get_or_create_sampler(
+    interface: &WebGPUInterface,
+    sampler: &engine::scene::SceneSampler,
+) -> std::rc::Rc<wgpu::Sampler> {
+    let get_address_mode = |wrap: engine::scene::SamplerWrap| -> wgpu::AddressMode {
+        match wrap {
+            engine::scene::SamplerWrap::Repeat => wgpu::AddressMode::Repeat,
+            engine::scene::SamplerWrap::ClampToEdge => wgpu::AddressMode::ClampToEdge,
+            engine::scene::SamplerWrap::MirroredRepeat => wgpu::AddressMode::MirrorRepeat,
+        }
+    };
+    let get_filter_mode = |filter: engine::scene::SamplerFilter| -> wgpu::FilterMode {
+        match filter {
+            engine::scene::SamplerFilter::Linear => wgpu::FilterMode::Linear,
+            engine::scene::SamplerFilter::Nearest => wgpu::FilterMode::Nearest,
+        }
+    };
+
+    // Materials share only a handful of sampler states, reuse them
+    let mut sampler_cache = interface.sampler_cache.borrow_mut();
+    let sampler_resource = sampler_cache.entry(*sampler).or_insert_with(|| {
+        std::rc::Rc::new(
+            interface.device.create_sampler(&wgpu::SamplerDescriptor {
+                address_mode_u: get_address_mode(sampler.wrap_u),
+                address_mode_v: get_address_mode(sampler.wrap_v),
+                address_mode_w: wgpu::AddressMode::ClampToEdge,
+                mag_filter: get_filter_mode(sampler.mag_filter),
+                min_filter: get_filter_mode(sampler.min_filter),
+                mipmap_filter: get_filter_mode(
+                    sampler
+                        .mipmap_filter
+                        .unwrap_or(engine::scene::SamplerFilter::Nearest),
+                ),
+                // without a mipmap filter only the base level is sampled
+                lod_max_clamp: if sampler.mipmap_filter.is_some() {
+                    32.0
+                } else {
+                    0.0
+                },
+                ..Default::default()
+            }),
+        )
+    });
+
+    return sampler_resource.clone();
+}
+
+// Rows of offset * rotation * scale, the texCoord set rides in the first row's w
+fn get_texture_transform_data(transform: &engine::scene::SceneTextureTransform) -> [f32; 8] {
+    let cos: f32 = transform.rotation.cos();
+    let sin: f32 = transform.rotation.sin();
+
+    return [
+        cos * transform.scale[0],
+        sin * transform.scale[1],
+        transform.offset[0],
+        transform.tex_coord as f32,
+        -sin * transform.scale[0],
+        cos * transform.scale[1],
+        transform.offset[1],
+        0.0,
+    ];
+}
+
+fn get_primitive_topology(topology: common::Topology) -> wgpu::PrimitiveTopology {
+    match topology {
+        common::Topology::Triangles => wgpu::PrimitiveTopology::TriangleList,
+        common::Topology::Lines => wgpu::PrimitiveTopology::LineList,
+        common::Topology::Points => wgpu::PrimitiveTopology::PointList,
+    }
+}
+
+fn create_gbuffers_pipeline(
+    interface: &WebGPUInterface,
+    shader: &wgpu::ShaderModule,
+    pipeline_layout: &wgpu::PipelineLayout,
+    vertex_buffers: &[wgpu::VertexBufferLayout<'_>],
+    vs_entry_point: &str,
+    cull_mode: Option<wgpu::Face>,
+    topology: common::Topology,
+) -> wgpu::RenderPipeline {
     let render_pipeline: wgpu::RenderPipeline =
         interface
             .device
             .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
                 label: None,
-                layout: Some(&pipeline_layout),
+                layout: Some(pipeline_layout),
                 vertex: wgpu::VertexState {
-                    module: &shader,
-                    entry_point: Some(define::VS_ENTRY_POINT),
+                    module: shader,
+                    entry_point: Some(vs_entry_point),
                     compilation_options: Default::default(),
-                    buffers: &vertex_buffers,
+                    buffers: vertex_buffers,
                 },
                 fragment: Some(wgpu::FragmentState {
-                    module: &shader,
+                    module: shader,
                     entry_point: Some(define::FS_ENTRY_POINT),
                     compilation_options: Default::default(),
                     targets: &[
@@ -1478,8 +1886,9 @@ fn init_differed_gbuffers_shading(
                     ],
                 }),
                 primitive: wgpu::PrimitiveState {
+                    topology: get_primitive_topology(topology),
                     front_face: wgpu::FrontFace::Ccw,
-                    cull_mode: Some(WEBGPU_CULL_MODE),
+                    cull_mode: cull_mode,
                     ..Default::default()
                 },
                 depth_stencil: Some(wgpu::DepthStencilState {
@@ -1493,21 +1902,306 @@ fn init_differed_gbuffers_shading(
                 multiview: None,
                 cache: None,
             });
-    let index_count: u32 = index_data.len() as u32;
 
-    let render_resource: WebGPURenderResource = WebGPURenderResource {
-        _shader: shader,
-        vertex_buf,
-        index_buf,
-        index_count,
-        bind_group: uniform_bind_group,
-        _bind_group_layout: uniform_bind_group_layout,
-        bind_group_2: Some(texture_bind_group),
-        uniform_buf,
-        render_pipeline,
-    };
+    return render_pipeline;
+}
 
-    return render_resource;
+fn create_blend_pipeline(
+    interface: &WebGPUInterface,
+    vertex_buffers: &[wgpu::VertexBufferLayout<'_>],
+    uniform_bind_group_layout: &wgpu::BindGroupLayout,
+    texture_bind_group_layout: &wgpu::BindGroupLayout,
+    vs_entry_point: &str,
+    cull_mode: Option<wgpu::Face>,
+    topology: common::Topology,
+) -> wgpu::RenderPipeline {
+    let shader: wgpu::ShaderModule =
+        interface
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: None,
+                source: wgpu::ShaderSource::Wgsl(std::borrow::Cow::Borrowed(concat!(
+                    include_str!("../shader/mesh.wgsl"),
+                    include_str!("../shader/lighting.wgsl"),
+                    include_str!("../shader/forward_blend.wgsl")
+                ))),
+            });
+
+    // Same layout as the differed uniform so its bind group can be reused
+    let lighting_bind_group_layout: wgpu::BindGroupLayout = interface
+        .device
+        .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: None,
+            entries: &get_lighting_layout_entries(),
+        });
+
+    let pipeline_layout: wgpu::PipelineLayout =
+        interface
+            .device
+            .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: None,
+                bind_group_layouts: &[
+                    uniform_bind_group_layout,
+                    texture_bind_group_layout,
+                    &lighting_bind_group_layout,
+                ],
+                push_constant_ranges: &[],
+            });
+
+    let render_pipeline: wgpu::RenderPipeline =
+        interface
+            .device
+            .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some("Blend pipeline"),
+                layout: Some(&pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: Some(vs_entry_point),
+                    compilation_options: Default::default(),
+                    buffers: vertex_buffers,
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: Some(define::FS_ENTRY_POINT),
+                    compilation_options: Default::default(),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format: interface.swapchain_format,
+                        blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                        write_mask: wgpu::ColorWrites::all(),
+                    })],
+                }),
+                primitive: wgpu::PrimitiveState {
+                    topology: get_primitive_topology(topology),
+                    front_face: wgpu::FrontFace::Ccw,
+                    cull_mode: cull_mode,
+                    ..Default::default()
+                },
+                depth_stencil: Some(wgpu::DepthStencilState {
+                    format: WEBGPU_DEPTH_FORMAT,
+                    depth_write_enabled: false,
+                    depth_compare: wgpu::CompareFunction::Less,
+                    stencil: wgpu::StencilState::default(),
+                    bias: wgpu::DepthBiasState::default(),
+                }),
+                multisample: wgpu::MultisampleState::default(),
+                multiview: None,
+                cache: None,
+            });
+
+    return render_pipeline;
+}
+
+fn draw_gbuffers_object(
+    gbuffer_pass: &mut wgpu::RenderPass<'_>,
+    object: &engine::scene::SceneObject,
+) {
+    let render_resource = object.render_resource.as_ref().unwrap().borrow();
+
+    gbuffer_pass.set_bind_group(0, &render_resource.bind_group, &[]);
+    gbuffer_pass.set_index_buffer(
+        render_resource.index_buf.slice(..),
+        wgpu::IndexFormat::Uint32,
+    );
+    gbuffer_pass.set_vertex_buffer(0, render_resource.vertex_buf.slice(..));
+
+    // One draw per submesh, each with its own material textures
+    for submesh in render_resource.submeshes.iter() {
+        if submesh.is_blend {
+            continue;
+        }
+        gbuffer_pass.set_pipeline(&submesh.pipeline);
+        gbuffer_pass.set_bind_group(1, &submesh.bind_group, &[]);
+        gbuffer_pass.draw_indexed(
+            submesh.index_start..submesh.index_start + submesh.index_count,
+            0,
+            0..1,
+        );
+    }
+}
+
+fn draw_blend_objects(
+    blend_pass: &mut wgpu::RenderPass<'_>,
+    scene_value: &engine::scene::Scene,
+    objects: &[&engine::scene::SceneObject],
+    lighting_bind_group: &wgpu::BindGroup,
+) {
+    struct BlendDraw<'a> {
+        object: &'a engine::scene::SceneObject,
+        submesh_index: usize,
+        distance: f32,
+    }
+
+    // Collect blended submeshes and sort them back to front
+    let mut blend_draws: Vec<BlendDraw<'_>> = Vec::new();
+    for object in objects.iter() {
+        if object.shading_type != 0 {
+            continue;
+        }
+        let model_matrix: glam::Mat4 = get_model_matrix(scene_value, object);
+        let render_resource = object.render_resource.as_ref().unwrap().borrow();
+        for (submesh_index, submesh) in render_resource.submeshes.iter().enumerate() {
+            if submesh.is_blend {
+                let center: glam::Vec3 =
+                    model_matrix.transform_point3(glam::Vec3::from_array(submesh.center));
+                blend_draws.push(BlendDraw {
+                    object: object,
+                    submesh_index: submesh_index,
+                    distance: center.distance(scene_value.eye_location),
+                });
+            }
+        }
+    }
+    blend_draws.sort_by(|a, b| b.distance.total_cmp(&a.distance));
+
+    blend_pass.set_bind_group(2, lighting_bind_group, &[]);
+    for blend_draw in blend_draws.iter() {
+        let render_resource = blend_draw.object.render_resource.as_ref().unwrap().borrow();
+        let submesh: &WebGPUSubMeshResource = &render_resource.submeshes[blend_draw.submesh_index];
+
+        blend_pass.set_pipeline(&submesh.pipeline);
+        blend_pass.set_bind_group(0, &render_resource.bind_group, &[]);
+        blend_pass.set_bind_group(1, &submesh.bind_group, &[]);
+        blend_pass.set_index_buffer(
+            render_resource.index_buf.slice(..),
+            wgpu::IndexFormat::Uint32,
+        );
+        blend_pass.set_vertex_buffer(0, render_resource.vertex_buf.slice(..));
+        blend_pass.draw_indexed(
+            submesh.index_start..submesh.index_start + submesh.index_count,
+            0,
+            0..1,
+        );
+    }
+}
+
+// Differed uniform and light list, shared by the differed resolve and the blend pass
+fn get_lighting_layout_entries() -> [wgpu::BindGroupLayoutEntry; 3] {
+    return [
+        wgpu::BindGroupLayoutEntry {
+            binding: 0,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        },
+        wgpu::BindGroupLayoutEntry {
+            binding: 1,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only: true },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        },
+        wgpu::BindGroupLayoutEntry {
+            binding: 2,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only: true },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        },
+    ];
+}
+
+// unlit flag, specular color * factor, clearcoat factor and roughness, sheen color and roughness
+fn get_material_buffer_data(scene_value: &engine::scene::Scene) -> Vec<[f32; 16]> {
+    let mut material_data: Vec<[f32; 16]> = Vec::with_capacity(scene_value.materials.len());
+    for material in scene_value
+        .materials
+        .iter()
+        .take(define::MAX_MATERIAL_COUNT)
+    {
+        let specular: [f32; 3] = material
+            .specular_color_factor
+            .map(|color| color * material.specular_factor);
+        material_data.push([
+            if material.unlit { 1.0 } else { 0.0 },
+            0.0,
+            0.0,
+            0.0,
+            specular[0],
+            specular[1],
+            specular[2],
+            0.0,
+            material.clearcoat_factor,
+            material.clearcoat_roughness_factor,
+            0.0,
+            0.0,
+            material.sheen_color_factor[0],
+            material.sheen_color_factor[1],
+            material.sheen_color_factor[2],
+            material.sheen_roughness_factor,
+        ]);
+    }
+
+    return material_data;
+}
+
+// position (w = type), direction (w = range), color * intensity, cone scale and offset
+fn get_light_buffer_data(scene_value: &engine::scene::Scene) -> Vec<[f32; 16]> {
+    let mut light_data: Vec<[f32; 16]> = Vec::with_capacity(scene_value.lights.len());
+    for light in scene_value.lights.iter().take(define::MAX_LIGHT_COUNT) {
+        let light_matrix: glam::Mat4 =
+            get_model_matrix(scene_value, &scene_value.objects[light.node as usize]);
+        let position: glam::Vec3 = light_matrix.transform_point3(glam::Vec3::ZERO);
+        // lights shine along their local -Z
+        let direction: glam::Vec3 = light_matrix
+            .transform_vector3(-glam::Vec3::Z)
+            .normalize_or_zero();
+
+        let light_type: f32 = match light.kind {
+            engine::scene::LightKind::Directional => 0.0,
+            engine::scene::LightKind::Point => 1.0,
+            engine::scene::LightKind::Spot => 2.0,
+        };
+        let cone_scale: f32 =
+            1.0 / (light.inner_cone_angle.cos() - light.outer_cone_angle.cos()).max(0.001);
+        let cone_offset: f32 = -light.outer_cone_angle.cos() * cone_scale;
+
+        light_data.push([
+            position.x,
+            position.y,
+            position.z,
+            light_type,
+            direction.x,
+            direction.y,
+            direction.z,
+            light.range.unwrap_or(0.0),
+            light.color[0] * light.intensity,
+            light.color[1] * light.intensity,
+            light.color[2] * light.intensity,
+            0.0,
+            cone_scale,
+            cone_offset,
+            0.0,
+            0.0,
+        ]);
+    }
+
+    return light_data;
+}
+
+fn get_model_matrix(
+    scene_value: &engine::scene::Scene,
+    object: &engine::scene::SceneObject,
+) -> glam::Mat4 {
+    let mut model_matrix = glam::Mat4::from_cols_array_2d(&object.world_transform);
+
+    // Force Y-up to Z-up
+    if scene_value.convert_y_to_z {
+        let y_to_z_mat: glam::Mat4 =
+            glam::Mat4::from_axis_angle(glam::Vec3::new(1.0, 0.0, 0.0), std::f32::consts::PI / 2.0);
+        model_matrix = y_to_z_mat * model_matrix;
+    }
+
+    return model_matrix;
 }
 
 fn update_differed_gbuffers_shading(
@@ -1527,19 +2221,11 @@ fn update_differed_gbuffers_shading(
     let eye: glam::Vec3 = scene_value.eye_location;
     let direction: glam::Vec3 = scene_value.eye_direction;
 
-    let mut model_matrix = glam::Mat4::from_cols_array_2d(&object.world_transform);
-
-    // Force Y-up to Z-up
-    if scene_value.convert_y_to_z {
-        let y_to_z_mat: glam::Mat4 =
-            glam::Mat4::from_axis_angle(glam::Vec3::new(1.0, 0.0, 0.0), std::f32::consts::PI / 2.0);
-        model_matrix = y_to_z_mat * model_matrix;
-    }
+    let model_matrix: glam::Mat4 = get_model_matrix(&scene_value, object);
 
     // Create matrices and write buffer
     let view_matrix = glam::Mat4::look_to_rh(eye, direction, glam::Vec3::Z);
-    let projection_matrix: glam::Mat4 =
-        glam::Mat4::perspective_rh(std::f32::consts::FRAC_PI_4, aspect_ratio, 0.01, 100.0);
+    let projection_matrix: glam::Mat4 = scene_value.get_projection_matrix(aspect_ratio);
 
     let rotaton_matrix: glam::Mat4 =
         glam::Mat4::from_quat(model_matrix.to_scale_rotation_translation().1);
@@ -1559,6 +2245,37 @@ fn update_differed_gbuffers_shading(
         0,
         bytemuck::cast_slice(uniform_ref),
     );
+
+    // Skinning
+    if let Some(joint_buf) = object
+        .render_resource
+        .as_ref()
+        .unwrap()
+        .borrow()
+        .joint_buf
+        .as_ref()
+    {
+        let joint_matrices: Vec<[f32; 16]> = scene_value.get_joint_matrices(object);
+        interface
+            .queue
+            .write_buffer(joint_buf, 0, bytemuck::cast_slice(&joint_matrices));
+    }
+
+    // Morph targets
+    if let Some(morph_weight_buf) = object
+        .render_resource
+        .as_ref()
+        .unwrap()
+        .borrow()
+        .morph_weight_buf
+        .as_ref()
+    {
+        interface.queue.write_buffer(
+            morph_weight_buf,
+            0,
+            bytemuck::cast_slice(&object.morph_weights),
+        );
+    }
 }
 
 pub fn init_differed_pipeline(interface: &WebGPUInterface) -> WebGPUDifferedResource {
@@ -1566,12 +2283,28 @@ pub fn init_differed_pipeline(interface: &WebGPUInterface) -> WebGPUDifferedReso
         _directional_light: [f32; 4],
         _ambient_light: [f32; 4],
         _inverse_matrix: [f32; 16],
+        _eye_location: [f32; 4],
         _debug: DifferedDebugUniform,
     }
 
     struct DifferedDebugUniform {
         _buffer_type: f32,
-        _padding: [f32; 3],
+        _light_count: f32,
+        _padding: [f32; 2],
+    }
+
+    struct DifferedLight {
+        _position: [f32; 4],
+        _direction: [f32; 4],
+        _color: [f32; 4],
+        _cone: [f32; 4],
+    }
+
+    struct DifferedMaterial {
+        _model: [f32; 4],
+        _specular: [f32; 4],
+        _clearcoat: [f32; 4],
+        _sheen: [f32; 4],
     }
 
     let shader: wgpu::ShaderModule =
@@ -1579,8 +2312,9 @@ pub fn init_differed_pipeline(interface: &WebGPUInterface) -> WebGPUDifferedReso
             .device
             .create_shader_module(wgpu::ShaderModuleDescriptor {
                 label: None,
-                source: wgpu::ShaderSource::Wgsl(std::borrow::Cow::Borrowed(include_str!(
-                    "../shader/differed.wgsl"
+                source: wgpu::ShaderSource::Wgsl(std::borrow::Cow::Borrowed(concat!(
+                    include_str!("../shader/lighting.wgsl"),
+                    include_str!("../shader/differed.wgsl")
                 ))),
             });
 
@@ -1596,9 +2330,9 @@ pub fn init_differed_pipeline(interface: &WebGPUInterface) -> WebGPUDifferedReso
 
     // Gbuffers
 
-    let gbuffer_position_texture: wgpu::Texture =
+    let gbuffer_normal_texture: wgpu::Texture =
         interface.device.create_texture(&wgpu::TextureDescriptor {
-            label: Some("position texture"),
+            label: Some("normal texture"),
             size: wgpu::Extent3d {
                 width: width,
                 height: height,
@@ -1612,9 +2346,9 @@ pub fn init_differed_pipeline(interface: &WebGPUInterface) -> WebGPUDifferedReso
             view_formats: &[],
         });
 
-    let gbuffer_normal_texture: wgpu::Texture =
+    let gbuffer_albedo_texture: wgpu::Texture =
         interface.device.create_texture(&wgpu::TextureDescriptor {
-            label: Some("normal texture"),
+            label: Some("albedo texture"),
             size: wgpu::Extent3d {
                 width: width,
                 height: height,
@@ -1628,9 +2362,9 @@ pub fn init_differed_pipeline(interface: &WebGPUInterface) -> WebGPUDifferedReso
             view_formats: &[],
         });
 
-    let gbuffer_albedo_texture: wgpu::Texture =
+    let gbuffer_metallic_roughness_texture: wgpu::Texture =
         interface.device.create_texture(&wgpu::TextureDescriptor {
-            label: Some("albedo texture"),
+            label: Some("metallic roughness texture"),
             size: wgpu::Extent3d {
                 width: width,
                 height: height,
@@ -1644,21 +2378,21 @@ pub fn init_differed_pipeline(interface: &WebGPUInterface) -> WebGPUDifferedReso
             view_formats: &[],
         });
 
-    let gbuffer_metallic_roughness_texture: wgpu::Texture =
-    interface.device.create_texture(&wgpu::TextureDescriptor {
-        label: Some("metallic roughness texture"),
-        size: wgpu::Extent3d {
-            width: width,
-            height: height,
-            depth_or_array_layers: 1,
-        },
-        mip_level_count: 1,
-        sample_count: 1,
-        dimension: wgpu::TextureDimension::D2,
-        format: wgpu::TextureFormat::Rgba16Float,
-        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
-        view_formats: &[],
-    });
+    let gbuffer_emissive_texture: wgpu::Texture =
+        interface.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("emissive texture"),
+            size: wgpu::Extent3d {
+                width: width,
+                height: height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba16Float,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
 
     // bindings
 
@@ -1681,7 +2415,7 @@ pub fn init_differed_pipeline(interface: &WebGPUInterface) -> WebGPUDifferedReso
                     binding: 1,
                     visibility: wgpu::ShaderStages::FRAGMENT,
                     ty: wgpu::BindingType::Texture {
-                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        sample_type: wgpu::TextureSampleType::Depth,
                         view_dimension: wgpu::TextureViewDimension::D2,
                         multisampled: false,
                     },
@@ -1691,7 +2425,7 @@ pub fn init_differed_pipeline(interface: &WebGPUInterface) -> WebGPUDifferedReso
                     binding: 2,
                     visibility: wgpu::ShaderStages::FRAGMENT,
                     ty: wgpu::BindingType::Texture {
-                        sample_type: wgpu::TextureSampleType::Depth,
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
                         view_dimension: wgpu::TextureViewDimension::D2,
                         multisampled: false,
                     },
@@ -1729,36 +2463,36 @@ pub fn init_differed_pipeline(interface: &WebGPUInterface) -> WebGPUDifferedReso
                     wgpu::BindGroupEntry {
                         binding: 0,
                         resource: wgpu::BindingResource::TextureView(
-                            &gbuffer_position_texture
+                            &gbuffer_normal_texture
                                 .create_view(&wgpu::TextureViewDescriptor::default()),
                         ),
                     },
                     wgpu::BindGroupEntry {
                         binding: 1,
                         resource: wgpu::BindingResource::TextureView(
-                            &gbuffer_normal_texture
+                            &interface
+                                .depth_texture
                                 .create_view(&wgpu::TextureViewDescriptor::default()),
                         ),
                     },
                     wgpu::BindGroupEntry {
                         binding: 2,
                         resource: wgpu::BindingResource::TextureView(
-                            &interface
-                                .depth_texture
+                            &gbuffer_albedo_texture
                                 .create_view(&wgpu::TextureViewDescriptor::default()),
                         ),
                     },
                     wgpu::BindGroupEntry {
                         binding: 3,
                         resource: wgpu::BindingResource::TextureView(
-                            &gbuffer_albedo_texture
+                            &gbuffer_metallic_roughness_texture
                                 .create_view(&wgpu::TextureViewDescriptor::default()),
                         ),
                     },
                     wgpu::BindGroupEntry {
                         binding: 4,
                         resource: wgpu::BindingResource::TextureView(
-                            &gbuffer_metallic_roughness_texture
+                            &gbuffer_emissive_texture
                                 .create_view(&wgpu::TextureViewDescriptor::default()),
                         ),
                     },
@@ -1774,20 +2508,27 @@ pub fn init_differed_pipeline(interface: &WebGPUInterface) -> WebGPUDifferedReso
         mapped_at_creation: false,
     });
 
+    // Punctual lights of every scene, the uniform holds how many are in use
+    let light_buf: wgpu::Buffer = interface.device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Differed light buffer"),
+        size: (define::MAX_LIGHT_COUNT * std::mem::size_of::<DifferedLight>()) as u64,
+        usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+
+    // Extension lobes of every material, looked up by the material index in the gbuffer
+    let material_buf: wgpu::Buffer = interface.device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Differed material buffer"),
+        size: (define::MAX_MATERIAL_COUNT * std::mem::size_of::<DifferedMaterial>()) as u64,
+        usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+
     let uniform_bind_group_layout: wgpu::BindGroupLayout = interface
         .device
         .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
             label: None,
-            entries: &[wgpu::BindGroupLayoutEntry {
-                binding: 0,
-                visibility: wgpu::ShaderStages::FRAGMENT,
-                ty: wgpu::BindingType::Buffer {
-                    ty: wgpu::BufferBindingType::Uniform,
-                    has_dynamic_offset: false,
-                    min_binding_size: None,
-                },
-                count: None,
-            }],
+            entries: &get_lighting_layout_entries(),
         });
 
     let uniform_bind_group: wgpu::BindGroup =
@@ -1795,10 +2536,20 @@ pub fn init_differed_pipeline(interface: &WebGPUInterface) -> WebGPUDifferedReso
             .device
             .create_bind_group(&wgpu::BindGroupDescriptor {
                 layout: &uniform_bind_group_layout,
-                entries: &[wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: uniform_buf.as_entire_binding(),
-                }],
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: uniform_buf.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: light_buf.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: material_buf.as_entire_binding(),
+                    },
+                ],
                 label: Some("Bind group 1"),
             });
 
@@ -1877,12 +2628,14 @@ pub fn init_differed_pipeline(interface: &WebGPUInterface) -> WebGPUDifferedReso
 
     let resource: WebGPUDifferedResource = WebGPUDifferedResource {
         _shader: shader,
-        gbuffer_position_texture,
         gbuffer_normal_texture,
         gbuffer_albedo_texture,
         gbuffer_metallic_roughness_texture,
+        gbuffer_emissive_texture,
         bind_groups,
         uniform_buf,
+        light_buf,
+        material_buf,
         render_pipeline,
         debug_pipeline,
     };
@@ -1910,8 +2663,7 @@ pub fn update_differed_buffer(
 
     // Create matrices and write buffer
     let view_matrix = glam::Mat4::look_to_rh(eye, direction, glam::Vec3::Z);
-    let projection_matrix: glam::Mat4 =
-        glam::Mat4::perspective_rh(std::f32::consts::FRAC_PI_4, aspect_ratio, 0.01, 100.0);
+    let projection_matrix: glam::Mat4 = scene_value.get_projection_matrix(aspect_ratio);
     let transform_matrix: glam::Mat4 = projection_matrix * view_matrix;
 
     let directional: [f32; 3] = scene_value.directional_light_angle;
@@ -1923,10 +2675,32 @@ pub fn update_differed_buffer(
     uniform_total.extend_from_slice(&[0.0]); // Padding!
     uniform_total.extend_from_slice(&ambient);
     uniform_total.extend_from_slice(&inverse_projection.to_cols_array().to_vec());
-    uniform_total.extend_from_slice(&[scene_value.differed_debug_type as f32, 0.0, 0.0, 0.0]);
+    uniform_total.extend_from_slice(&[eye.x, eye.y, eye.z, 0.0]);
+    let light_data: Vec<[f32; 16]> = get_light_buffer_data(&scene_value);
+    uniform_total.extend_from_slice(&[
+        scene_value.differed_debug_type as f32,
+        light_data.len() as f32,
+        0.0,
+        0.0,
+    ]);
 
     let uniform_ref: &[f32] = uniform_total.as_ref();
     interface
         .queue
         .write_buffer(&resource.uniform_buf, 0, bytemuck::cast_slice(uniform_ref));
+
+    if light_data.len() > 0 {
+        interface
+            .queue
+            .write_buffer(&resource.light_buf, 0, bytemuck::cast_slice(&light_data));
+    }
+
+    let material_data: Vec<[f32; 16]> = get_material_buffer_data(&scene_value);
+    if material_data.len() > 0 {
+        interface.queue.write_buffer(
+            &resource.material_buf,
+            0,
+            bytemuck::cast_slice(&material_data),
+        );
+    }
 }
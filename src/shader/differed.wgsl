@@ -1,3 +1,5 @@
+// Differed resolve, appended to lighting.wgsl
+
 @vertex
 fn vs_main( @builtin(vertex_index) VertexIndex : u32 ) -> @builtin(position) vec4f 
 {
@@ -9,58 +11,77 @@ fn vs_main( @builtin(vertex_index) VertexIndex : u32 ) -> @builtin(position) vec
   return vec4f(pos[VertexIndex], 0.0, 1.0);
 }
 
-struct Uniform
+@group(0) @binding(0) var gbuffer_normal   : texture_2d<f32>;
+@group(0) @binding(1) var gbuffer_depth    : texture_depth_2d;
+@group(0) @binding(2) var gbuffer_albedo   : texture_2d<f32>;
+@group(0) @binding(3) var gbuffer_metallic : texture_2d<f32>;
+@group(0) @binding(4) var gbuffer_emissive : texture_2d<f32>;
+@group(1) @binding(0) var<uniform> inUniform: LightingUniform;
+@group(1) @binding(1) var<storage, read> lights : array<Light>;
+@group(1) @binding(2) var<storage, read> materials : array<Material>;
+
+// Rebuild the world position from the depth buffer instead of storing it in a gbuffer
+fn world_from_depth(coord : vec2<f32>, depth : f32) -> vec3<f32>
 {
-    directional_light  : vec4<f32>,
-    ambient_light      : vec4<f32>,
-    inverse_matrix     : mat4x4<f32>,
-    buffer_type        : f32,
-}
+    let size     : vec2<f32> = vec2<f32>(textureDimensions(gbuffer_depth));
+    let ndc      : vec2<f32> = vec2<f32>(coord.x / size.x * 2.0 - 1.0, 1.0 - coord.y / size.y * 2.0);
+    let position : vec4<f32> = inUniform.inverse_matrix * vec4<f32>(ndc, depth, 1.0);
 
-@group(0) @binding(0) var gbuffer_position : texture_2d<f32>;
-@group(0) @binding(1) var gbuffer_normal   : texture_2d<f32>;
-@group(0) @binding(2) var gbuffer_depth    : texture_depth_2d;
-@group(0) @binding(3) var gbuffer_albedo   : texture_2d<f32>;
-@group(0) @binding(4) var gbuffer_metallic : texture_2d<f32>;
-@group(1) @binding(0) var<uniform> inUniform: Uniform;
+    return position.xyz / position.w;
+}
 
 @fragment
 fn fs_main( @builtin(position) coord : vec4f ) -> @location(0) vec4f
 {
-    let position : vec4f     = textureLoad( gbuffer_position, vec2i(floor(coord.xy)), 0 );
     var normal   : vec3<f32> = textureLoad( gbuffer_normal, vec2i(floor(coord.xy)), 0 ).xyz;
     var depth    : f32       = textureLoad( gbuffer_depth, vec2i(floor(coord.xy)), 0 );
     var albedo   : vec4<f32> = textureLoad( gbuffer_albedo, vec2i(floor(coord.xy)), 0 );
+    let metallic : vec4<f32> = textureLoad( gbuffer_metallic, vec2i(floor(coord.xy)), 0 );
+    let emissive : vec4<f32> = textureLoad( gbuffer_emissive, vec2i(floor(coord.xy)), 0 );
 
     if (depth >= 1.0) 
     {
       discard;
     }
 
+    // the material index is stored in the alpha of the metallic gbuffer
+    let material : Material = materials[min(u32(metallic.a), arrayLength(&materials) - 1u)];
+
+    // KHR_materials_unlit
+    if (material.model.x == 1.0)
+    {
+        return albedo;
+    }
+
+    let position : vec3<f32> = world_from_depth(coord.xy, depth);
+
     let directional_light : vec3<f32> = normalize(inUniform.directional_light.xyz);
-    let diffuse           : f32       = max(dot(-1.0 * directional_light, normal), 0.0);
+    let view              : vec3<f32> = normalize(position - inUniform.eye_location.xyz);
 
-    let view     : vec3<f32> = normalize((inUniform.inverse_matrix * position).xyz);
-    let halfway  : vec3<f32> = -normalize(directional_light.xyz + view);
-    let specular : f32       = pow(max(dot(normal, halfway), 0.0), 100.0);
+    // occlusion is stored in the red channel of the metallic gbuffer
+    let occlusion         : f32       = metallic.r;
+    let ambient_light     : vec4<f32> = inUniform.ambient_light * occlusion;
 
-    let ambient_light     : vec4<f32> = inUniform.ambient_light;
+    var frag_color : vec3<f32> = shade(material, albedo.rgb, normal, -directional_light, view) + ambient_light.rgb;
 
-    let surface_color  : vec4<f32> = albedo;
-    let specular_color : vec4<f32> = vec4(1.0, 1.0, 1.0, 1.0);
+    // punctual lights
+    for (var i = 0u; i < u32(inUniform.light_count); i++)
+    {
+        let light : LightSample = sample_light(lights[i], position);
+        frag_color += light.radiance * shade(material, albedo.rgb, normal, light.direction, view);
+    }
 
-    var frag_color = diffuse * surface_color + specular * specular_color + ambient_light;
-    return frag_color;
+    return vec4<f32>(frag_color + emissive.rgb, 1.0);
 }
 
 @fragment
 fn fs_debug_main( @builtin(position) coord : vec4f ) -> @location(0) vec4f
 {
-    let position : vec4f     = textureLoad( gbuffer_position, vec2i(floor(coord.xy)), 0 );
     var normal   : vec3<f32> = textureLoad( gbuffer_normal, vec2i(floor(coord.xy)), 0 ).xyz;
     var depth    : f32       = textureLoad( gbuffer_depth, vec2i(floor(coord.xy)), 0 );
     let albedo   : vec4<f32> = textureLoad( gbuffer_albedo, vec2i(floor(coord.xy)), 0 );
     let metallic : vec4<f32> = textureLoad( gbuffer_metallic, vec2i(floor(coord.xy)), 0 );
+    let emissive : vec4<f32> = textureLoad( gbuffer_emissive, vec2i(floor(coord.xy)), 0 );
 
     normal.x = (normal.x + 1.0) * 0.5;
     normal.y = (normal.y + 1.0) * 0.5;
@@ -85,6 +106,10 @@ fn fs_debug_main( @builtin(position) coord : vec4f ) -> @location(0) vec4f
     {
       return metallic;
     }
+    else if(inUniform.buffer_type == 5.0)
+    {
+      return vec4(emissive.rgb, 1.0);
+    }
 
     return vec4(depth, 0.0, 0.0, 1.0);
 }
\ No newline at end of file
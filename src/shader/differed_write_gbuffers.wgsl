@@ -1,67 +1,52 @@
-struct VertexOutput {
-    @builtin(position) position   : vec4<f32>,
-	@location(0)       normal     : vec3<f32>,
-    @location(1)       uv         : vec2<f32>,
-    @location(2)       tangent    : vec3<f32>,
-};
+// Gbuffer pass fragment stage, appended to mesh.wgsl
 
 struct FragmentOutput {
-    @location(0) position : vec4<f32>,
-    @location(1) normal   : vec4<f32>,
-    @location(2) albedo   : vec4<f32>,
-    @location(3) metallic : vec4<f32>,
-}
-
-struct Uniform {
-    model_matrix      : mat4x4<f32>,
-    view_matrix       : mat4x4<f32>,
-    projection_matrix : mat4x4<f32>,
-    rotation_matrix   : mat4x4<f32>,
-}
-
-@group(0) @binding(0) var<uniform> inUniform : Uniform;
-@group(1) @binding(0) var base_color_texture : texture_2d<f32>;
-@group(1) @binding(1) var base_color_sampler : sampler;
-@group(1) @binding(2) var normal_texture     : texture_2d<f32>;
-@group(1) @binding(3) var normal_sampler     : sampler;
-@group(1) @binding(4) var metallic_roughness_texture     : texture_2d<f32>;
-@group(1) @binding(5) var metallic_roughness_sampler     : sampler;
-
-@vertex
-fn vs_main(
-    @location(0) position : vec4<f32>,
-    @location(1) normal   : vec3<f32>,
-    @location(2) uv       : vec2<f32>,
-    @location(3) tangent  : vec3<f32>,
-) -> VertexOutput 
-{
-    let normal_world   = normalize(inUniform.rotation_matrix * vec4<f32>(normal, 1.0)).xyz;
-	let tangent_world  = normalize(inUniform.rotation_matrix * vec4<f32>(tangent, 1.0)).xyz;
-
-    var output : VertexOutput;
-
-    output.position  = inUniform.projection_matrix * inUniform.view_matrix * inUniform.model_matrix * position;
-    output.normal    = normal_world;
-    output.uv        = uv;
-    output.tangent   = tangent_world;
-
-    return output;
+    @location(0) normal   : vec4<f32>,
+    @location(1) albedo   : vec4<f32>,
+    @location(2) metallic : vec4<f32>,
+    @location(3) emissive : vec4<f32>,
 }
 
 @fragment
-fn fs_main(vertex: VertexOutput) -> FragmentOutput 
+fn fs_main(vertex: VertexOutput, @builtin(front_facing) front_facing : bool) -> FragmentOutput 
 {
-	let binormal_world = normalize(cross(vertex.normal, vertex.tangent));
-	let tbn_matrix     = mat3x3<f32>(vertex.tangent, binormal_world, vertex.normal);
-    let encoded_normal = textureSample(normal_texture, normal_sampler, vertex.uv).rgb;
+    let base_color_uv         = get_texture_uv(0u, vertex.uv, vertex.uv1);
+    let normal_uv             = get_texture_uv(1u, vertex.uv, vertex.uv1);
+    let metallic_roughness_uv = get_texture_uv(2u, vertex.uv, vertex.uv1);
+    let emissive_uv           = get_texture_uv(3u, vertex.uv, vertex.uv1);
+    let occlusion_uv          = get_texture_uv(4u, vertex.uv, vertex.uv1);
+
+    // back faces of double sided materials see the flipped tangent frame
+    let face_sign      = select(-1.0, 1.0, front_facing);
+    let normal_world   = vertex.normal * face_sign;
+    let tangent_world  = vertex.tangent.xyz * face_sign;
+	let binormal_world = normalize(cross(vertex.normal, vertex.tangent.xyz)) * sign(vertex.tangent.w) * face_sign;
+	let tbn_matrix     = mat3x3<f32>(tangent_world, binormal_world, normal_world);
+    let encoded_normal = textureSample(normal_texture, normal_sampler, normal_uv).rgb;
     let surface_normal = normalize(encoded_normal - 0.5);
 
 	var output : FragmentOutput;
 
-    output.position = vertex.position;
     output.normal   = vec4<f32>(normalize(tbn_matrix * surface_normal), 1.0);
-    output.albedo   = textureSample(base_color_texture, base_color_sampler, vertex.uv);
-    output.metallic = textureSample(metallic_roughness_texture, metallic_roughness_sampler, vertex.uv);
+    // base color factor x texture x COLOR_0
+    output.albedo   = textureSample(base_color_texture, base_color_sampler, base_color_uv) * inMaterial.base_color_factor * vertex.color;
+
+    // alpha mask, cutoff is zero for opaque materials
+    if (output.albedo.a < inMaterial.alpha_cutoff)
+    {
+        discard;
+    }
+
+    // glTF packs roughness in green and metalness in blue, occlusion goes to red
+    let metallic_roughness = textureSample(metallic_roughness_texture, metallic_roughness_sampler, metallic_roughness_uv);
+    let occlusion          = textureSample(occlusion_texture, occlusion_sampler, occlusion_uv).r;
+    output.metallic = vec4<f32>(
+        mix(1.0, occlusion, inMaterial.occlusion_strength),
+        metallic_roughness.g * inMaterial.roughness_factor,
+        metallic_roughness.b * inMaterial.metallic_factor,
+        inMaterial.material_index,
+    );
+    output.emissive = textureSample(emissive_texture, emissive_sampler, emissive_uv) * inMaterial.emissive_factor;
 
     return output;
 }
\ No newline at end of file
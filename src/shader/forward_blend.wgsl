@@ -0,0 +1,51 @@
+// Blend pass fragment stage, appended to mesh.wgsl and lighting.wgsl
+
+@group(2) @binding(0) var<uniform> inLighting : LightingUniform;
+@group(2) @binding(1) var<storage, read> lights : array<Light>;
+@group(2) @binding(2) var<storage, read> materials : array<Material>;
+
+// Same lighting as the differed resolve, for surfaces that can not live in the gbuffer
+@fragment
+fn fs_main(vertex: VertexOutput, @builtin(front_facing) front_facing : bool) -> @location(0) vec4<f32>
+{
+    let base_color_uv = get_texture_uv(0u, vertex.uv, vertex.uv1);
+    let normal_uv     = get_texture_uv(1u, vertex.uv, vertex.uv1);
+    let emissive_uv   = get_texture_uv(3u, vertex.uv, vertex.uv1);
+    let occlusion_uv  = get_texture_uv(4u, vertex.uv, vertex.uv1);
+
+    // back faces of double sided materials see the flipped tangent frame
+    let face_sign      = select(-1.0, 1.0, front_facing);
+    let normal_world   = vertex.normal * face_sign;
+    let tangent_world  = vertex.tangent.xyz * face_sign;
+	let binormal_world = normalize(cross(vertex.normal, vertex.tangent.xyz)) * sign(vertex.tangent.w) * face_sign;
+	let tbn_matrix     = mat3x3<f32>(tangent_world, binormal_world, normal_world);
+    let encoded_normal = textureSample(normal_texture, normal_sampler, normal_uv).rgb;
+    let normal         = normalize(tbn_matrix * normalize(encoded_normal - 0.5));
+
+    let albedo    : vec4<f32> = textureSample(base_color_texture, base_color_sampler, base_color_uv) * inMaterial.base_color_factor * vertex.color;
+    let emissive  : vec4<f32> = textureSample(emissive_texture, emissive_sampler, emissive_uv) * inMaterial.emissive_factor;
+    let occlusion : f32       = mix(1.0, textureSample(occlusion_texture, occlusion_sampler, occlusion_uv).r, inMaterial.occlusion_strength);
+
+    let material : Material = materials[min(u32(inMaterial.material_index), arrayLength(&materials) - 1u)];
+
+    // KHR_materials_unlit
+    if (material.model.x == 1.0)
+    {
+        return albedo;
+    }
+
+    let directional_light : vec3<f32> = normalize(inLighting.directional_light.xyz);
+    let view              : vec3<f32> = normalize(vertex.world - inLighting.eye_location.xyz);
+    let ambient_light     : vec4<f32> = inLighting.ambient_light * occlusion;
+
+    var frag_color : vec3<f32> = shade(material, albedo.rgb, normal, -directional_light, view) + ambient_light.rgb;
+
+    // punctual lights
+    for (var i = 0u; i < u32(inLighting.light_count); i++)
+    {
+        let light : LightSample = sample_light(lights[i], vertex.world);
+        frag_color += light.radiance * shade(material, albedo.rgb, normal, light.direction, view);
+    }
+
+    return vec4<f32>(frag_color + emissive.rgb, albedo.a);
+}
@@ -0,0 +1,101 @@
+// Light and material lobes shared by the differed resolve and the blend pass
+
+struct LightingUniform
+{
+    directional_light  : vec4<f32>,
+    ambient_light      : vec4<f32>,
+    inverse_matrix     : mat4x4<f32>,
+    eye_location       : vec4<f32>,
+    buffer_type        : f32,
+    // lights in use, the light buffer holds MAX_LIGHT_COUNT
+    light_count        : f32,
+}
+
+struct Light
+{
+    position  : vec4<f32>,
+    direction : vec4<f32>,
+    color     : vec4<f32>,
+    cone      : vec4<f32>,
+}
+
+struct Material
+{
+    // x is 1 for KHR_materials_unlit
+    model     : vec4<f32>,
+    // KHR_materials_specular, color times strength
+    specular  : vec4<f32>,
+    // KHR_materials_clearcoat, x factor and y roughness
+    clearcoat : vec4<f32>,
+    // KHR_materials_sheen, rgb color and w roughness
+    sheen     : vec4<f32>,
+}
+
+struct LightSample
+{
+    direction : vec3<f32>,
+    radiance  : vec3<f32>,
+}
+
+// KHR_lights_punctual, inverse square falloff windowed by range and a smooth spot cone
+fn sample_light(light : Light, position : vec3<f32>) -> LightSample
+{
+    var output : LightSample;
+
+    // directional
+    if (light.position.w == 0.0)
+    {
+        output.direction = -light.direction.xyz;
+        output.radiance  = light.color.rgb;
+        return output;
+    }
+
+    let to_light : vec3<f32> = light.position.xyz - position;
+    let distance : f32       = max(length(to_light), 0.0001);
+    output.direction = to_light / distance;
+
+    var attenuation : f32 = 1.0 / (distance * distance);
+    if (light.direction.w > 0.0)
+    {
+        attenuation *= clamp(1.0 - pow(distance / light.direction.w, 4.0), 0.0, 1.0);
+    }
+
+    // spot
+    if (light.position.w == 2.0)
+    {
+        let cone : f32 = clamp(dot(light.direction.xyz, -output.direction) * light.cone.x + light.cone.y, 0.0, 1.0);
+        attenuation *= cone * cone;
+    }
+
+    output.radiance = light.color.rgb * attenuation;
+    return output;
+}
+
+const PI : f32 = 3.14159265359;
+
+// Blinn-Phong base under the sheen, clearcoat and specular lobes of the material extensions
+fn shade(material : Material, albedo : vec3<f32>, normal : vec3<f32>, light_direction : vec3<f32>, view : vec3<f32>) -> vec3<f32>
+{
+    let halfway : vec3<f32> = normalize(light_direction - view);
+    let n_dot_l : f32       = max(dot(normal, light_direction), 0.0);
+    let n_dot_v : f32       = max(dot(normal, -view), 0.0);
+    let n_dot_h : f32       = max(dot(normal, halfway), 0.0);
+
+    // KHR_materials_specular is approximated, its color times strength tints a fixed exponent
+    // highlight instead of scaling the F0 of a microfacet specular lobe
+    let base : vec3<f32> = n_dot_l * albedo + pow(n_dot_h, 100.0) * material.specular.rgb;
+
+    // sheen, Charlie distribution with the Neubelt visibility
+    let sheen_alpha        : f32       = max(material.sheen.w * material.sheen.w, 0.0001);
+    let sin_halfway        : f32       = sqrt(max(1.0 - n_dot_h * n_dot_h, 0.0));
+    let sheen_distribution : f32       = (2.0 + 1.0 / sheen_alpha) * pow(sin_halfway, 1.0 / sheen_alpha) / (2.0 * PI);
+    let sheen_visibility   : f32       = 1.0 / max(4.0 * (n_dot_l + n_dot_v - n_dot_l * n_dot_v), 0.0001);
+    let sheen              : vec3<f32> = material.sheen.rgb * sheen_distribution * sheen_visibility * n_dot_l;
+
+    // clearcoat, a dielectric layer (F0 0.04) with the Blinn-Phong exponent of its roughness
+    let clearcoat_fresnel : f32 = (0.04 + 0.96 * pow(1.0 - n_dot_v, 5.0)) * material.clearcoat.x;
+    let clearcoat_power   : f32 = 2.0 / max(pow(material.clearcoat.y, 4.0), 0.0001) - 2.0;
+    let clearcoat         : f32 = clearcoat_fresnel * pow(n_dot_h, clearcoat_power) * n_dot_l;
+
+    return (base + sheen) * (1.0 - clearcoat_fresnel) + clearcoat;
+}
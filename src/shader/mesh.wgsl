@@ -0,0 +1,192 @@
+// Vertex stage and material bindings shared by the gbuffer and blend passes
+
+struct VertexOutput {
+    @builtin(position) position   : vec4<f32>,
+	@location(0)       normal     : vec3<f32>,
+    @location(1)       uv         : vec2<f32>,
+    @location(2)       tangent    : vec4<f32>,
+    @location(3)       world      : vec3<f32>,
+    @location(4)       color      : vec4<f32>,
+    @location(5)       uv1        : vec2<f32>,
+};
+
+struct Uniform {
+    model_matrix      : mat4x4<f32>,
+    view_matrix       : mat4x4<f32>,
+    projection_matrix : mat4x4<f32>,
+    rotation_matrix   : mat4x4<f32>,
+}
+
+// Rows of the KHR_texture_transform matrix, u_row.w selects the texCoord set
+struct TextureTransform {
+    u_row : vec4<f32>,
+    v_row : vec4<f32>,
+}
+
+struct MaterialUniform {
+    base_color_factor  : vec4<f32>,
+    emissive_factor    : vec4<f32>,
+    metallic_factor    : f32,
+    roughness_factor   : f32,
+    occlusion_strength : f32,
+    alpha_cutoff       : f32,
+    // index into the extension lobes of every material
+    material_index     : f32,
+    // base color, normal, metallic roughness, emissive, occlusion
+    texture_transforms : array<TextureTransform, 5>,
+}
+
+@group(0) @binding(0) var<uniform> inUniform : Uniform;
+@group(0) @binding(1) var<storage, read> joint_matrices : array<mat4x4<f32>>;
+@group(0) @binding(2) var<storage, read> morph_deltas   : array<vec4<f32>>;
+@group(0) @binding(3) var<storage, read> morph_weights  : array<f32>;
+@group(1) @binding(0) var base_color_texture : texture_2d<f32>;
+@group(1) @binding(1) var base_color_sampler : sampler;
+@group(1) @binding(2) var normal_texture     : texture_2d<f32>;
+@group(1) @binding(3) var normal_sampler     : sampler;
+@group(1) @binding(4) var metallic_roughness_texture     : texture_2d<f32>;
+@group(1) @binding(5) var metallic_roughness_sampler     : sampler;
+@group(1) @binding(6) var<uniform> inMaterial : MaterialUniform;
+@group(1) @binding(7) var emissive_texture   : texture_2d<f32>;
+@group(1) @binding(8) var emissive_sampler   : sampler;
+@group(1) @binding(9) var occlusion_texture  : texture_2d<f32>;
+@group(1) @binding(10) var occlusion_sampler : sampler;
+
+fn transform_vertex(position : vec4<f32>, normal : vec3<f32>, uv : vec2<f32>, uv1 : vec2<f32>, tangent : vec4<f32>, color : vec4<f32>) -> VertexOutput
+{
+    let normal_world   = normalize(inUniform.rotation_matrix * vec4<f32>(normal, 1.0)).xyz;
+	let tangent_world  = normalize(inUniform.rotation_matrix * vec4<f32>(tangent.xyz, 1.0)).xyz;
+    let world          = inUniform.model_matrix * position;
+
+    var output : VertexOutput;
+
+    output.position  = inUniform.projection_matrix * inUniform.view_matrix * world;
+    output.normal    = normal_world;
+    output.uv        = uv;
+    output.uv1       = uv1;
+    output.tangent   = vec4<f32>(tangent_world, tangent.w);
+    output.color     = color;
+    output.world     = world.xyz / world.w;
+
+    return output;
+}
+
+struct MorphedVertex {
+    position : vec4<f32>,
+    normal   : vec3<f32>,
+    tangent  : vec4<f32>,
+};
+
+fn get_skin_matrix(joints : vec4<u32>, weights : vec4<f32>) -> mat4x4<f32>
+{
+    return weights.x * joint_matrices[joints.x]
+         + weights.y * joint_matrices[joints.y]
+         + weights.z * joint_matrices[joints.z]
+         + weights.w * joint_matrices[joints.w];
+}
+
+// morph deltas hold (position, normal, tangent) of every vertex, target after target
+fn morph_vertex(vertex_index : u32, position : vec4<f32>, normal : vec3<f32>, tangent : vec4<f32>) -> MorphedVertex
+{
+    let target_count = arrayLength(&morph_weights);
+    let vertex_count = arrayLength(&morph_deltas) / (3u * target_count);
+
+    var output : MorphedVertex;
+    output.position = position;
+    output.normal   = normal;
+    output.tangent  = tangent;
+
+    for (var i = 0u; i < target_count; i++)
+    {
+        let weight = morph_weights[i];
+        let delta  = (i * vertex_count + vertex_index) * 3u;
+        output.position += vec4<f32>(weight * morph_deltas[delta].xyz, 0.0);
+        output.normal   += weight * morph_deltas[delta + 1u].xyz;
+        output.tangent  += vec4<f32>(weight * morph_deltas[delta + 2u].xyz, 0.0);
+    }
+
+    return output;
+}
+
+// KHR_texture_transform applied to the texCoord set of the slot
+fn get_texture_uv(slot : u32, uv : vec2<f32>, uv1 : vec2<f32>) -> vec2<f32>
+{
+    let transform = inMaterial.texture_transforms[slot];
+    let source    = vec3<f32>(select(uv, uv1, transform.u_row.w > 0.5), 1.0);
+
+    return vec2<f32>(dot(transform.u_row.xyz, source), dot(transform.v_row.xyz, source));
+}
+
+@vertex
+fn vs_main(
+    @location(0) position : vec4<f32>,
+    @location(1) normal   : vec3<f32>,
+    @location(2) uv       : vec2<f32>,
+    @location(3) tangent  : vec4<f32>,
+    @location(6) color    : vec4<f32>,
+    @location(7) uv1      : vec2<f32>,
+) -> VertexOutput 
+{
+    return transform_vertex(position, normal, uv, uv1, tangent, color);
+}
+
+@vertex
+fn vs_skinned_main(
+    @location(0) position : vec4<f32>,
+    @location(1) normal   : vec3<f32>,
+    @location(2) uv       : vec2<f32>,
+    @location(3) tangent  : vec4<f32>,
+    @location(4) joints   : vec4<u32>,
+    @location(5) weights  : vec4<f32>,
+    @location(6) color    : vec4<f32>,
+    @location(7) uv1      : vec2<f32>,
+) -> VertexOutput 
+{
+    let skin_matrix = get_skin_matrix(joints, weights);
+
+    let skinned_position = skin_matrix * position;
+    let skinned_normal   = (skin_matrix * vec4<f32>(normal, 0.0)).xyz;
+    let skinned_tangent  = (skin_matrix * vec4<f32>(tangent.xyz, 0.0)).xyz;
+
+    return transform_vertex(skinned_position, skinned_normal, uv, uv1, vec4<f32>(skinned_tangent, tangent.w), color);
+}
+
+@vertex
+fn vs_morph_main(
+    @builtin(vertex_index) vertex_index : u32,
+    @location(0) position : vec4<f32>,
+    @location(1) normal   : vec3<f32>,
+    @location(2) uv       : vec2<f32>,
+    @location(3) tangent  : vec4<f32>,
+    @location(6) color    : vec4<f32>,
+    @location(7) uv1      : vec2<f32>,
+) -> VertexOutput 
+{
+    let morphed = morph_vertex(vertex_index, position, normal, tangent);
+
+    return transform_vertex(morphed.position, morphed.normal, uv, uv1, morphed.tangent, color);
+}
+
+@vertex
+fn vs_skinned_morph_main(
+    @builtin(vertex_index) vertex_index : u32,
+    @location(0) position : vec4<f32>,
+    @location(1) normal   : vec3<f32>,
+    @location(2) uv       : vec2<f32>,
+    @location(3) tangent  : vec4<f32>,
+    @location(4) joints   : vec4<u32>,
+    @location(5) weights  : vec4<f32>,
+    @location(6) color    : vec4<f32>,
+    @location(7) uv1      : vec2<f32>,
+) -> VertexOutput 
+{
+    // morph targets apply before skinning
+    let morphed     = morph_vertex(vertex_index, position, normal, tangent);
+    let skin_matrix = get_skin_matrix(joints, weights);
+
+    let skinned_position = skin_matrix * morphed.position;
+    let skinned_normal   = (skin_matrix * vec4<f32>(morphed.normal, 0.0)).xyz;
+    let skinned_tangent  = (skin_matrix * vec4<f32>(morphed.tangent.xyz, 0.0)).xyz;
+
+    return transform_vertex(skinned_position, skinned_normal, uv, uv1, vec4<f32>(skinned_tangent, tangent.w), color);
+}